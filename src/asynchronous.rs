@@ -0,0 +1,202 @@
+//! Async support, enabled with the `tokio` feature.
+//!
+//! - [ProgressStream] wraps any [Stream] the way [ProgressIterator](crate::iter::ProgressIterator) wraps iterators
+//! - [ProgressReader] and [ProgressWriter] implement tokio's [AsyncRead], [AsyncBufRead], [AsyncWrite] and [AsyncSeek]
+//! - The `*_async` functions mirror the [global](crate::global) API, waiting on tokio's blocking thread pool instead of an executor worker when the global bar is in use.
+//!   They follow the [ErrorPolicy](crate::global::ErrorPolicy) when there is no global bar
+//!
+//! Wrapped streams, readers and writers must be [Unpin]; pin them with [Box::pin] or [std::pin::pin!] otherwise.
+//!
+//! # Example
+//!
+//! ```
+//! use progress_bar::{pb::ProgressBar, Units};
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let data = vec![0u8; 1 << 20];
+//! let mut progress_bar = ProgressBar::with_writer(data.len(), std::io::stderr());
+//! progress_bar.set_units(Units::BinaryBytes);
+//!
+//! let mut reader = progress_bar.wrap_read(&data[..]);
+//! tokio::io::copy(&mut reader, &mut tokio::io::sink()).await.unwrap();
+//! reader.progress_bar().finalize();
+//! # });
+//! ```
+
+use crate::global::{report, take_progress_bar, with_progress_bar, ProgressBarError, CURRENT_PROGRESS_BAR};
+use crate::io::{ProgressReader, ProgressWriter};
+use crate::iter::ProgressBarIter;
+use crate::pb::ProgressBar;
+use crate::style::{Color, Style};
+use futures_core::Stream;
+use std::io::{self, IoSlice, SeekFrom};
+use std::pin::Pin;
+use std::sync::TryLockError;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncSeek, AsyncWrite, ReadBuf};
+
+/// Wraps streams into [ProgressBarIter], which increments a bar on each item
+pub trait ProgressStream: Stream + Sized {
+    /// Wrap the stream in a new bar on stdout.
+    /// The maximum is the length of the stream when known exactly, otherwise the bar is indeterminate.
+    fn progress(self) -> ProgressBarIter<Self> {
+        let progress_bar = match self.size_hint() {
+            (lower, Some(upper)) if lower == upper => ProgressBar::new(upper),
+            _ => ProgressBar::new_indeterminate(),
+        };
+        self.progress_with(progress_bar)
+    }
+
+    /// Wrap the stream in `progress_bar`, which can be configured beforehand
+    fn progress_with(self, progress_bar: ProgressBar) -> ProgressBarIter<Self> {
+        ProgressBarIter { iter: self, progress_bar, finished: false }
+    }
+}
+
+impl<S: Stream> ProgressStream for S {}
+
+impl<S: Stream + Unpin> Stream for ProgressBarIter<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        match ready!(Pin::new(&mut self.iter).poll_next(cx)) {
+            Some(item) => {
+                self.progress_bar.inc();
+                Poll::Ready(Some(item))
+            }
+            None => {
+                self.finish();
+                Poll::Ready(None)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for ProgressReader<R> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let before = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        let read = buf.filled().len() - before;
+        self.progress_bar.inc_by(read as u64);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncBufRead for ProgressReader<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        Pin::new(&mut self.get_mut().inner).poll_fill_buf(cx)
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        Pin::new(&mut self.inner).consume(amt);
+        self.progress_bar.inc_by(amt as u64);
+    }
+}
+
+impl<R: AsyncSeek + Unpin> AsyncSeek for ProgressReader<R> {
+    fn start_seek(mut self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        Pin::new(&mut self.inner).start_seek(position)
+    }
+
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        let position = ready!(Pin::new(&mut self.inner).poll_complete(cx))?;
+        self.progress_bar.set_position(position);
+        Poll::Ready(Ok(position))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for ProgressWriter<W> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let written = ready!(Pin::new(&mut self.inner).poll_write(cx, buf))?;
+        self.progress_bar.inc_by(written as u64);
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_write_vectored(mut self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>]) -> Poll<io::Result<usize>> {
+        let written = ready!(Pin::new(&mut self.inner).poll_write_vectored(cx, bufs))?;
+        self.progress_bar.inc_by(written as u64);
+        Poll::Ready(Ok(written))
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+impl<W: AsyncSeek + Unpin> AsyncSeek for ProgressWriter<W> {
+    fn start_seek(mut self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        Pin::new(&mut self.inner).start_seek(position)
+    }
+
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        let position = ready!(Pin::new(&mut self.inner).poll_complete(cx))?;
+        self.progress_bar.set_position(position);
+        Poll::Ready(Ok(position))
+    }
+}
+
+/// Run `f` on the blocking thread pool, so that waiting doesn't hold up an executor worker
+async fn unblock<R: Send + 'static>(f: impl FnOnce() -> R + Send + 'static) -> R {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(error) => std::panic::resume_unwind(error.into_panic()),
+    }
+}
+
+/// Run `f` on the global progress bar.
+/// If another thread holds it, the lock is awaited on tokio's blocking thread pool.
+pub async fn with_progress_bar_async<R: Send + 'static>(f: impl FnOnce(&mut ProgressBar) -> R + Send + 'static) -> Result<R, ProgressBarError> {
+    match CURRENT_PROGRESS_BAR.try_lock() {
+        Ok(mut current) => current.as_mut().map(f).ok_or(ProgressBarError::NoProgressBar),
+        Err(TryLockError::Poisoned(_)) => Err(ProgressBarError::Poisoned),
+        Err(TryLockError::WouldBlock) => unblock(move || with_progress_bar(f)).await,
+    }
+}
+
+pub async fn set_progress_bar_progress_async(progress: usize) {
+    report("set progress bar progress", with_progress_bar_async(move |progress_bar| progress_bar.set_progress(progress)).await);
+}
+
+pub async fn inc_progress_bar_async() {
+    report("increase progress bar progress", with_progress_bar_async(ProgressBar::inc).await);
+}
+
+pub async fn inc_progress_bar_by_async(n: u64) {
+    report("increase progress bar progress", with_progress_bar_async(move |progress_bar| progress_bar.inc_by(n)).await);
+}
+
+pub async fn set_progress_bar_action_async(action: &str, color: Color, style: Style) {
+    let action = action.to_string();
+    report("set progress bar action", with_progress_bar_async(move |progress_bar| progress_bar.set_action(&action, color, style)).await);
+}
+
+pub async fn set_progress_bar_message_async(message: &str) {
+    let message = message.to_string();
+    report("set progress bar message", with_progress_bar_async(move |progress_bar| progress_bar.set_message(&message)).await);
+}
+
+pub async fn print_progress_bar_info_async(info_name: &str, text: &str, info_color: Color, info_style: Style) {
+    let (info_name, text) = (info_name.to_string(), text.to_string());
+    report("print progress bar info", with_progress_bar_async(move |progress_bar| progress_bar.print_info(&info_name, &text, info_color, info_style)).await);
+}
+
+pub async fn finalize_progress_bar_async() {
+    let progress_bar = match CURRENT_PROGRESS_BAR.try_lock() {
+        Ok(mut current) => current.take().ok_or(ProgressBarError::NoProgressBar),
+        Err(TryLockError::Poisoned(_)) => Err(ProgressBarError::Poisoned),
+        Err(TryLockError::WouldBlock) => unblock(take_progress_bar).await,
+    };
+    report("finalize progress bar", progress_bar.map(|mut progress_bar| progress_bar.finalize()));
+}
@@ -0,0 +1,162 @@
+//! Estimators of the progress rate, from which the ETA is computed.
+//!
+//! | Estimator | Behavior |
+//! |---|---|
+//! | [Linear] (default) | Average rate over the whole run |
+//! | [Ewma] | Exponentially weighted moving average, favoring recent updates |
+//! | [SlidingWindow] | Average rate over a recent period of time |
+//!
+//! Custom estimators can be used by implementing [Estimator].
+//!
+//! # Example
+//!
+//! ```
+//! use progress_bar::{pb::ProgressBar, estimator::Ewma};
+//! use std::time::Duration;
+//!
+//! let mut progress_bar = ProgressBar::new_with_eta(100);
+//! progress_bar.set_estimator(Ewma::new(Duration::from_secs(5)));
+//! ```
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Estimates how many steps are completed per second, from the updates of a progress bar
+pub trait Estimator: Send {
+    /// Forget previous updates, as progress was reset to 0 at `now`
+    fn reset(&mut self, now: Instant);
+
+    /// Record that `progress` steps were completed at `now`
+    fn record(&mut self, progress: u64, now: Instant);
+
+    /// Estimated number of steps per second at `now`, or `None` if there is not enough data yet
+    fn rate(&self, now: Instant) -> Option<f64>;
+}
+
+/// Steps per second between two samples, `None` if no time elapsed
+fn rate_between(from: (u64, Instant), to: (u64, Instant)) -> Option<f64> {
+    let secs = to.1.saturating_duration_since(from.1).as_secs_f64();
+    if secs <= 0. {
+        return None;
+    }
+    Some(to.0.saturating_sub(from.0) as f64 / secs)
+}
+
+/// Average rate since the last reset.
+/// Accurate when the rate is steady, but slow to react to changes.
+#[derive(Debug, Clone)]
+pub struct Linear {
+    start: Instant,
+    progress: u64,
+}
+
+impl Linear {
+    pub fn new() -> Self {
+        Linear { start: Instant::now(), progress: 0 }
+    }
+}
+
+impl Default for Linear {
+    fn default() -> Self {
+        Linear::new()
+    }
+}
+
+impl Estimator for Linear {
+    fn reset(&mut self, now: Instant) {
+        self.start = now;
+        self.progress = 0;
+    }
+
+    fn record(&mut self, progress: u64, _now: Instant) {
+        self.progress = progress;
+    }
+
+    fn rate(&self, now: Instant) -> Option<f64> {
+        rate_between((0, self.start), (self.progress, now))
+    }
+}
+
+/// Exponentially weighted moving average of the rate.
+/// The weight of an update is halved every `half_life`, so the estimate follows changes of throughput.
+#[derive(Debug, Clone)]
+pub struct Ewma {
+    half_life: Duration,
+    last: (u64, Instant),
+    value: Option<f64>,
+}
+
+impl Ewma {
+    pub fn new(half_life: Duration) -> Self {
+        Ewma { half_life, last: (0, Instant::now()), value: None }
+    }
+}
+
+impl Estimator for Ewma {
+    fn reset(&mut self, now: Instant) {
+        self.last = (0, now);
+        self.value = None;
+    }
+
+    fn record(&mut self, progress: u64, now: Instant) {
+        // Updates made at the same instant are merged into the next sample
+        let Some(sample) = rate_between(self.last, (progress, now)) else {
+            return;
+        };
+        let elapsed = now.saturating_duration_since(self.last.1).as_secs_f64();
+        let weight = match self.half_life.as_secs_f64() {
+            half_life if half_life > 0. => 1. - (-elapsed / half_life).exp2(),
+            _ => 1.,
+        };
+        self.value = Some(match self.value {
+            Some(value) => value + weight * (sample - value),
+            None => sample,
+        });
+        self.last = (progress, now);
+    }
+
+    fn rate(&self, _now: Instant) -> Option<f64> {
+        self.value
+    }
+}
+
+/// Average rate over the last `window` of time.
+/// Stalls are taken into account, as the rate drops when no progress is made.
+#[derive(Debug, Clone)]
+pub struct SlidingWindow {
+    window: Duration,
+    samples: VecDeque<(u64, Instant)>,
+}
+
+impl SlidingWindow {
+    pub fn new(window: Duration) -> Self {
+        SlidingWindow { window, samples: VecDeque::from([(0, Instant::now())]) }
+    }
+}
+
+impl Estimator for SlidingWindow {
+    fn reset(&mut self, now: Instant) {
+        self.samples.clear();
+        self.samples.push_back((0, now));
+    }
+
+    fn record(&mut self, progress: u64, now: Instant) {
+        // Samples are kept at least 1/32 of the window apart, to bound memory in hot loops
+        let len = self.samples.len();
+        if len >= 2 && now.saturating_duration_since(self.samples[len - 2].1) < self.window / 32 {
+            self.samples[len - 1] = (progress, now);
+        } else {
+            self.samples.push_back((progress, now));
+        }
+        // Keep the last sample before the window, so that it is always fully covered
+        while self.samples.len() > 2 && now.saturating_duration_since(self.samples[1].1) >= self.window {
+            self.samples.pop_front();
+        }
+    }
+
+    fn rate(&self, now: Instant) -> Option<f64> {
+        let first = *self.samples.front()?;
+        let last = *self.samples.back()?;
+        rate_between(first, (last.0, now))
+    }
+}
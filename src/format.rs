@@ -0,0 +1,77 @@
+//! Human-readable formatting of durations, counts and rates.
+
+use crate::style::Units;
+use std::time::Duration;
+
+/// Formats a duration the way a clock would, like `42s`, `3m07s` or `1h02m`
+pub(crate) fn duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Scales a number of bytes with the prefixes of `units`, like `1.5 KiB` or `1.5 kB`
+fn bytes(bytes: f64, units: Units) -> String {
+    let (base, prefixes) = match units {
+        Units::DecimalBytes => (1000., ["kB", "MB", "GB", "TB", "PB", "EB"]),
+        _ => (1024., ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"]),
+    };
+    // The prefix is chosen from the displayed value, so that rounding never shows `1024.0 KiB`
+    if bytes.round() < base {
+        return format!("{bytes:.0} B");
+    }
+    let mut value = bytes / base;
+    let mut prefix = prefixes[0];
+    for next in &prefixes[1..] {
+        if (value * 10.).round() / 10. < base {
+            break;
+        }
+        value /= base;
+        prefix = next;
+    }
+    format!("{value:.1} {prefix}")
+}
+
+/// Formats a progress or a maximum
+pub(crate) fn count(count: u64, units: Units) -> String {
+    match units {
+        Units::Items => count.to_string(),
+        units => bytes(count as f64, units),
+    }
+}
+
+/// Formats a rate, in bytes per second in byte mode
+pub(crate) fn rate_in(per_sec: f64, units: Units) -> String {
+    match units {
+        Units::Items => rate(per_sec),
+        units => format!("{}/s", bytes(per_sec, units)),
+    }
+}
+
+/// Formats a number of items per second, like `12.5/s`, `340/s` or `1.2M/s`.  
+/// Slow rates are given per minute or per hour instead, like `4.0/min`.
+pub(crate) fn rate(per_sec: f64) -> String {
+    const PREFIXES: [&str; 4] = ["k", "M", "G", "T"];
+
+    match per_sec {
+        r if r > 0. && r * 60. < 1. => format!("{:.1}/h", r * 3600.),
+        r if r > 0. && r < 1. => format!("{:.1}/min", r * 60.),
+        r if r < 100. => format!("{r:.1}/s"),
+        r if r < 1000. => format!("{r:.0}/s"),
+        mut r => {
+            let mut prefix = PREFIXES[0];
+            r /= 1000.;
+            for next in &PREFIXES[1..] {
+                if r < 1000. {
+                    break;
+                }
+                r /= 1000.;
+                prefix = next;
+            }
+            format!("{r:.1}{prefix}/s")
+        }
+    }
+}
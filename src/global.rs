@@ -1,14 +1,108 @@
-use std::sync::{LazyLock, Mutex};
-use crate::{pb::ProgressBar, style::{Color, Style}};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::Duration;
+use crate::{estimator::Estimator, multi::{MultiProgress, MultiProgressBar}, pb::ProgressBar, style::{BarGlyphs, BarMode, Color, Style, Units}, template::Template, ticker::{self, Ticker}};
 
 pub static CURRENT_PROGRESS_BAR: LazyLock<Mutex<Option<ProgressBar>>> = LazyLock::new(|| Mutex::new(None));
 
+/// Why a global progress bar could not be updated, returned by the `try_*` functions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressBarError {
+    /// No global progress bar is set
+    NoProgressBar,
+    /// No progress bar is registered under this name
+    NoProgressBarNamed(String),
+    /// A thread panicked while updating the progress bar.  
+    /// Setting a new global progress bar recovers from this.
+    Poisoned,
+}
+
+impl fmt::Display for ProgressBarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProgressBarError::NoProgressBar => write!(f, "no progress bar"),
+            ProgressBarError::NoProgressBarNamed(name) => write!(f, "no progress bar named {name}"),
+            ProgressBarError::Poisoned => write!(f, "progress bar poisoned by a panic"),
+        }
+    }
+}
+
+impl std::error::Error for ProgressBarError {}
+
+/// What the global functions do when they fail, such as when no progress bar is set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Print an error on stderr each time
+    #[default]
+    Warn,
+    /// Print an error on stderr the first time only
+    WarnOnce,
+    /// Ignore errors
+    Silent,
+    /// Panic with the error
+    Panic,
+}
+
+static ERROR_POLICY: Mutex<ErrorPolicy> = Mutex::new(ErrorPolicy::Warn);
+static WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Set what the global functions do when they fail (default: [ErrorPolicy::Warn]).  
+/// The `try_*` functions return errors instead, regardless of the policy.
+/// 
+/// # Example
+/// 
+/// ```
+/// use progress_bar::*;
+/// 
+/// set_progress_bar_error_policy(ErrorPolicy::Silent);
+/// inc_progress_bar(); // no progress bar, nothing is printed
+/// assert_eq!(try_inc_progress_bar(), Err(ProgressBarError::NoProgressBar));
+/// ```
+pub fn set_progress_bar_error_policy(policy: ErrorPolicy) {
+    *ERROR_POLICY.lock().unwrap_or_else(PoisonError::into_inner) = policy;
+}
+
+/// Handle the result of a global function according to the error policy
+pub(crate) fn report(action: &str, result: Result<(), ProgressBarError>) {
+    let Err(error) = result else {
+        return;
+    };
+    match *ERROR_POLICY.lock().unwrap_or_else(PoisonError::into_inner) {
+        ErrorPolicy::Warn => eprintln!("ERROR: Unable to {action} ({error})"),
+        ErrorPolicy::WarnOnce => if !WARNED.swap(true, Ordering::Relaxed) {
+            eprintln!("ERROR: Unable to {action} ({error})");
+        },
+        ErrorPolicy::Silent => (),
+        ErrorPolicy::Panic => panic!("Unable to {action} ({error})"),
+    }
+}
+
+/// Run `f` on the global progress bar
+pub(crate) fn with_progress_bar<R>(f: impl FnOnce(&mut ProgressBar) -> R) -> Result<R, ProgressBarError> {
+    let mut current = CURRENT_PROGRESS_BAR.lock().map_err(|_| ProgressBarError::Poisoned)?;
+    current.as_mut().map(f).ok_or(ProgressBarError::NoProgressBar)
+}
+
 pub fn has_progress_bar() -> bool {
-    CURRENT_PROGRESS_BAR.lock().unwrap().is_some()
+    CURRENT_PROGRESS_BAR.lock().unwrap_or_else(PoisonError::into_inner).is_some()
 }
 
+/// Incremented each time a global progress bar is set, so that a guard only finalizes its own bar
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
 pub fn set_progress_bar(progress_bar: ProgressBar) {
-    *CURRENT_PROGRESS_BAR.lock().unwrap() = Some(progress_bar);
+    install_progress_bar(progress_bar);
+}
+
+/// Set the global progress bar, returning its generation
+fn install_progress_bar(progress_bar: ProgressBar) -> u64 {
+    let mut current = CURRENT_PROGRESS_BAR.lock().unwrap_or_else(PoisonError::into_inner);
+    *current = Some(progress_bar);
+    CURRENT_PROGRESS_BAR.clear_poison();
+    GENERATION.fetch_add(1, Ordering::Relaxed) + 1
 }
 
 pub fn init_progress_bar(max: usize) {
@@ -16,83 +110,437 @@ pub fn init_progress_bar(max: usize) {
     set_progress_bar(progress_bar);
 }
 
+/// Finalizes the global progress bar when dropped, see [init_progress_bar_guarded].  
+/// Nothing happens if the bar was already finalized or replaced by another one.  
+/// If the thread is panicking, the bar is abandoned instead, see [ProgressBar::abandon].
+#[must_use = "the progress bar is finalized as soon as the guard is dropped"]
+pub struct ProgressBarGuard {
+    /// Generation of the bar this guard was created for
+    generation: u64,
+}
+
+impl Drop for ProgressBarGuard {
+    fn drop(&mut self) {
+        // The bar may have been finalized and replaced by another one in the meantime
+        let mut current = CURRENT_PROGRESS_BAR.lock().unwrap_or_else(PoisonError::into_inner);
+        let progress_bar = match GENERATION.load(Ordering::Relaxed) == self.generation {
+            true => current.take(),
+            false => None,
+        };
+        drop(current);
+        match progress_bar {
+            // The bar is abandoned by its own drop, if it was displayed
+            Some(progress_bar) if std::thread::panicking() => drop(progress_bar),
+            Some(mut progress_bar) => progress_bar.finalize(),
+            None => (),
+        }
+    }
+}
+
+/// Same as [set_progress_bar], but the bar is finalized when the returned guard is dropped,
+/// even if the function returns early or panics.
+pub fn set_progress_bar_guarded(progress_bar: ProgressBar) -> ProgressBarGuard {
+    ProgressBarGuard { generation: install_progress_bar(progress_bar) }
+}
+
+/// Same as [init_progress_bar], but the bar is finalized when the returned guard is dropped,
+/// even if the function returns early or panics.
+/// 
+/// # Example
+/// 
+/// ```
+/// use progress_bar::*;
+/// 
+/// fn load(pages: &[&str]) -> Result<(), String> {
+///     let _guard = init_progress_bar_guarded(pages.len());
+///     for page in pages {
+///         if page.is_empty() {
+///             return Err("empty page".to_string());
+///         }
+///         inc_progress_bar();
+///     }
+///     Ok(())
+/// }
+/// 
+/// assert!(load(&["https://example.com", ""]).is_err());
+/// assert!(!has_progress_bar());
+/// ```
+pub fn init_progress_bar_guarded(max: usize) -> ProgressBarGuard {
+    set_progress_bar_guarded(ProgressBar::new(max))
+}
+
 pub fn init_progress_bar_with_eta(max: usize) {
     let progress_bar = ProgressBar::new_with_eta(max);
     set_progress_bar(progress_bar);
 }
 
+/// Initializes a progress bar for an unknown number of actions, see [ProgressBar::new_indeterminate].
+pub fn init_indeterminate_progress_bar() {
+    let progress_bar = ProgressBar::new_indeterminate();
+    set_progress_bar(progress_bar);
+}
+
+/// Same as [init_progress_bar] but the bar and its logs are written to `writer` instead of stdout.
+pub fn init_progress_bar_with_writer(max: usize, writer: impl Write + Send + 'static) {
+    let progress_bar = ProgressBar::with_writer(max, writer);
+    set_progress_bar(progress_bar);
+}
+
 #[deprecated(note = "Use set_progress_bar_progress instead")]
 pub fn set_progress_bar_progression(progress: usize) {
     set_progress_bar_progress(progress);
 }
 
 pub fn set_progress_bar_progress(progress: usize) {
-    match *CURRENT_PROGRESS_BAR.lock().unwrap() {
-        Some(ref mut progress_bar) => progress_bar.set_progress(progress),
-        None => eprintln!("ERROR: Unable to set progress bar progress (no progress bar)"),
-    }
+    report("set progress bar progress", try_set_progress_bar_progress(progress));
+}
+
+pub fn try_set_progress_bar_progress(progress: usize) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.set_progress(progress))
 }
 
 pub fn inc_progress_bar() {
-    match *CURRENT_PROGRESS_BAR.lock().unwrap() {
-        Some(ref mut progress_bar) => progress_bar.inc(),
-        None => eprintln!("ERROR: Unable to increase progress bar progress (no progress bar)"),
-    }
+    report("increase progress bar progress", try_inc_progress_bar());
+}
+
+pub fn try_inc_progress_bar() -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.inc())
+}
+
+pub fn inc_progress_bar_by(n: u64) {
+    report("increase progress bar progress", try_inc_progress_bar_by(n));
+}
+
+pub fn try_inc_progress_bar_by(n: u64) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.inc_by(n))
+}
+
+pub fn tick_progress_bar() {
+    report("tick progress bar", try_tick_progress_bar());
+}
+
+pub fn try_tick_progress_bar() -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.tick())
+}
+
+/// Redraw the global progress bar every `interval` in a background thread.  
+/// The thread stops when the bar is finalized or when the returned [Ticker] is dropped.
+pub fn spawn_progress_bar_ticker(interval: Duration) -> Ticker {
+    Ticker::spawn(interval, || match ticker::try_lock(&CURRENT_PROGRESS_BAR) {
+        Some(mut current) => current.as_mut().is_some_and(ProgressBar::steady_tick),
+        None => true,
+    })
+}
+
+pub fn set_progress_bar_max_redraw_rate(per_second: Option<u32>) {
+    report("set progress bar max redraw rate", try_set_progress_bar_max_redraw_rate(per_second));
+}
+
+pub fn try_set_progress_bar_max_redraw_rate(per_second: Option<u32>) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.set_max_redraw_rate(per_second))
 }
 
 pub fn set_progress_bar_width(width: usize) {
-    match *CURRENT_PROGRESS_BAR.lock().unwrap() {
-        Some(ref mut progress_bar) => progress_bar.set_width(width),
-        None => eprintln!("ERROR: Unable to set progress bar width (no progress bar)"),
-    }
+    report("set progress bar width", try_set_progress_bar_width(width));
+}
+
+pub fn try_set_progress_bar_width(width: usize) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.set_width(width))
+}
+
+pub fn set_progress_bar_mode(mode: BarMode) {
+    report("set progress bar mode", try_set_progress_bar_mode(mode));
+}
+
+pub fn try_set_progress_bar_mode(mode: BarMode) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.set_bar_mode(mode))
+}
+
+pub fn set_progress_bar_glyphs(glyphs: BarGlyphs) {
+    report("set progress bar glyphs", try_set_progress_bar_glyphs(glyphs));
+}
+
+pub fn try_set_progress_bar_glyphs(glyphs: BarGlyphs) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.set_bar_glyphs(glyphs))
+}
+
+pub fn set_progress_bar_units(units: Units) {
+    report("set progress bar units", try_set_progress_bar_units(units));
+}
+
+pub fn try_set_progress_bar_units(units: Units) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.set_units(units))
+}
+
+pub fn set_progress_bar_template(template: Template) {
+    report("set progress bar template", try_set_progress_bar_template(template));
+}
+
+pub fn try_set_progress_bar_template(template: Template) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.set_template(template))
+}
+
+pub fn set_progress_bar_estimator<E: Estimator + 'static>(estimator: E) {
+    report("set progress bar estimator", try_set_progress_bar_estimator(estimator));
+}
+
+pub fn try_set_progress_bar_estimator<E: Estimator + 'static>(estimator: E) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.set_estimator(estimator))
 }
 
 pub fn set_progress_bar_max(max: usize) {
-    match *CURRENT_PROGRESS_BAR.lock().unwrap() {
-        Some(ref mut progress_bar) => progress_bar.set_max(max),
-        None => eprintln!("ERROR: Unable to set progress bar max (no progress bar)"),
-    }
+    report("set progress bar max", try_set_progress_bar_max(max));
+}
+
+pub fn try_set_progress_bar_max(max: usize) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.set_max(max))
 }
 
 /// Warning: This resets progress to 0
 pub fn enable_eta() {
-    match *CURRENT_PROGRESS_BAR.lock().unwrap() {
-        Some(ref mut progress_bar) => progress_bar.enable_eta(),
-        None => eprintln!("ERROR: Unable to set progress bar max (no progress bar)"),
-    }
+    report("enable progress bar ETA", try_enable_eta());
+}
+
+pub fn try_enable_eta() -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.enable_eta())
 }
 
 pub fn disable_eta() {
-    match *CURRENT_PROGRESS_BAR.lock().unwrap() {
-        Some(ref mut progress_bar) => progress_bar.disable_eta(),
-        None => eprintln!("ERROR: Unable to set progress bar max (no progress bar)"),
-    }
+    report("disable progress bar ETA", try_disable_eta());
+}
+
+pub fn try_disable_eta() -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.disable_eta())
+}
+
+pub fn enable_progress_bar_percent() {
+    report("enable progress bar percent", try_enable_progress_bar_percent());
+}
+
+pub fn try_enable_progress_bar_percent() -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.enable_percent())
+}
+
+pub fn disable_progress_bar_percent() {
+    report("disable progress bar percent", try_disable_progress_bar_percent());
+}
+
+pub fn try_disable_progress_bar_percent() -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.disable_percent())
+}
+
+pub fn enable_progress_bar_elapsed() {
+    report("enable progress bar elapsed time", try_enable_progress_bar_elapsed());
+}
+
+pub fn try_enable_progress_bar_elapsed() -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.enable_elapsed())
+}
+
+pub fn disable_progress_bar_elapsed() {
+    report("disable progress bar elapsed time", try_disable_progress_bar_elapsed());
+}
+
+pub fn try_disable_progress_bar_elapsed() -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.disable_elapsed())
+}
+
+pub fn enable_progress_bar_rate() {
+    report("enable progress bar rate", try_enable_progress_bar_rate());
+}
+
+pub fn try_enable_progress_bar_rate() -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.enable_rate())
+}
+
+pub fn disable_progress_bar_rate() {
+    report("disable progress bar rate", try_disable_progress_bar_rate());
+}
+
+pub fn try_disable_progress_bar_rate() -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.disable_rate())
 }
 
 pub fn print_progress_bar_info(info_name: &str, text: &str, info_color: Color, info_style: Style) {
-    match *CURRENT_PROGRESS_BAR.lock().unwrap() {
-        Some(ref mut progress_bar) => progress_bar.print_info(info_name, text, info_color, info_style),
-        None => eprintln!("ERROR: Unable to print progress bar info (no progress bar)"),
-    }
+    report("print progress bar info", try_print_progress_bar_info(info_name, text, info_color, info_style));
+}
+
+pub fn try_print_progress_bar_info(info_name: &str, text: &str, info_color: Color, info_style: Style) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.print_info(info_name, text, info_color, info_style))
 }
 
 pub fn set_progress_bar_action(action: &str, color: Color, style: Style) {
-    match *CURRENT_PROGRESS_BAR.lock().unwrap() {
-        Some(ref mut progress_bar) => progress_bar.set_action(action, color, style),
-        None => eprintln!("ERROR: Unable to set progress bar action (no progress bar)"),
-    }
+    report("set progress bar action", try_set_progress_bar_action(action, color, style));
+}
+
+pub fn try_set_progress_bar_action(action: &str, color: Color, style: Style) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.set_action(action, color, style))
+}
+
+pub fn set_progress_bar_message(message: &str) {
+    report("set progress bar message", try_set_progress_bar_message(message));
+}
+
+pub fn try_set_progress_bar_message(message: &str) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.set_message(message))
 }
 
 pub fn print_progress_bar_final_info(info_name: &str, text: &str, info_color: Color, info_style: Style) {
-    match *CURRENT_PROGRESS_BAR.lock().unwrap() {
-        Some(ref mut progress_bar) => progress_bar.print_final_info(info_name, text, info_color, info_style),
-        None => eprintln!("ERROR: Unable to print progress bar final info (no progress bar)"),
-    }
+    report("print progress bar final info", try_print_progress_bar_final_info(info_name, text, info_color, info_style));
+}
+
+pub fn try_print_progress_bar_final_info(info_name: &str, text: &str, info_color: Color, info_style: Style) -> Result<(), ProgressBarError> {
+    with_progress_bar(|progress_bar| progress_bar.print_final_info(info_name, text, info_color, info_style))
 }
 
 pub fn finalize_progress_bar() {
-    match CURRENT_PROGRESS_BAR.lock().unwrap().take() {
-        Some(mut progress_bar) => progress_bar.finalize(),
-        None => eprintln!("ERROR: Unable to finalize progress bar (no progress bar)"),
+    report("finalize progress bar", try_finalize_progress_bar());
+}
+
+pub fn try_finalize_progress_bar() -> Result<(), ProgressBarError> {
+    take_progress_bar()?.finalize();
+    Ok(())
+}
+
+/// Remove the global progress bar
+pub(crate) fn take_progress_bar() -> Result<ProgressBar, ProgressBarError> {
+    let progress_bar = CURRENT_PROGRESS_BAR.lock().map_err(|_| ProgressBarError::Poisoned)?.take();
+    progress_bar.ok_or(ProgressBarError::NoProgressBar)
+}
+
+/// Named global progress bars, displayed together in a [MultiProgress] block
+#[derive(Default)]
+struct Registry {
+    multi: Option<MultiProgress>,
+    progress_bars: HashMap<String, MultiProgressBar>,
+}
+
+static REGISTRY: LazyLock<Mutex<Registry>> = LazyLock::new(|| Mutex::new(Registry::default()));
+
+/// The block of named progress bars, if any bar is registered
+#[cfg(feature = "logger")]
+pub(crate) fn named_progress_bars() -> Option<MultiProgress> {
+    let registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    match registry.progress_bars.is_empty() {
+        true => None,
+        false => registry.multi.clone(),
+    }
+}
+
+fn named_progress_bar(name: &str) -> Result<MultiProgressBar, ProgressBarError> {
+    let registry = REGISTRY.lock().map_err(|_| ProgressBarError::Poisoned)?;
+    registry.progress_bars.get(name).cloned().ok_or_else(|| ProgressBarError::NoProgressBarNamed(name.to_string()))
+}
+
+/// Set the block in which named progress bars are displayed (default: a block on stdout).  
+/// This allows rendering them on another writer, or changing their redraw rate.
+/// Bars registered before keep being displayed in the previous block.
+pub fn set_progress_bar_registry(multi: MultiProgress) {
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner).multi = Some(multi);
+}
+
+/// Registers a global progress bar under `name`, so that independent modules can each own one.  
+/// All named bars are displayed together, and a bar already registered under `name` is finalized first.  
+/// The block of named bars is drawn separately from the unnamed global bar (see [init_progress_bar]), so don't display both at once.
+/// Logs of the `logger` feature are printed above the block.
+/// 
+/// # Example
+/// 
+/// ```
+/// use progress_bar::*;
+/// 
+/// init_progress_bar_named("download", 10);
+/// init_progress_bar_named("extract", 10);
+/// for _ in 0..10 {
+///     inc_progress_bar_named("download");
+///     inc_progress_bar_named("extract");
+/// }
+/// finalize_progress_bar_named("download");
+/// finalize_progress_bar_named("extract");
+/// ```
+pub fn init_progress_bar_named(name: &str, max: usize) {
+    register_progress_bar(name, |multi| multi.add(max));
+}
+
+pub fn init_indeterminate_progress_bar_named(name: &str) {
+    register_progress_bar(name, MultiProgress::add_indeterminate);
+}
+
+fn register_progress_bar(name: &str, add: impl FnOnce(&MultiProgress) -> MultiProgressBar) {
+    let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(previous) = registry.progress_bars.remove(name) {
+        previous.finalize();
     }
+    let progress_bar = add(registry.multi.get_or_insert_with(MultiProgress::new));
+    registry.progress_bars.insert(name.to_string(), progress_bar);
+}
+
+pub fn has_progress_bar_named(name: &str) -> bool {
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner).progress_bars.contains_key(name)
+}
+
+pub fn set_progress_bar_progress_named(name: &str, progress: usize) {
+    report("set progress bar progress", try_set_progress_bar_progress_named(name, progress));
+}
+
+pub fn try_set_progress_bar_progress_named(name: &str, progress: usize) -> Result<(), ProgressBarError> {
+    named_progress_bar(name).map(|progress_bar| progress_bar.set_progress(progress))
+}
+
+pub fn inc_progress_bar_named(name: &str) {
+    report("increase progress bar progress", try_inc_progress_bar_named(name));
+}
+
+pub fn try_inc_progress_bar_named(name: &str) -> Result<(), ProgressBarError> {
+    named_progress_bar(name).map(|progress_bar| progress_bar.inc())
+}
+
+pub fn inc_progress_bar_by_named(name: &str, n: u64) {
+    report("increase progress bar progress", try_inc_progress_bar_by_named(name, n));
+}
+
+pub fn try_inc_progress_bar_by_named(name: &str, n: u64) -> Result<(), ProgressBarError> {
+    named_progress_bar(name).map(|progress_bar| progress_bar.inc_by(n))
+}
+
+pub fn set_progress_bar_max_named(name: &str, max: usize) {
+    report("set progress bar max", try_set_progress_bar_max_named(name, max));
+}
+
+pub fn try_set_progress_bar_max_named(name: &str, max: usize) -> Result<(), ProgressBarError> {
+    named_progress_bar(name).map(|progress_bar| progress_bar.set_max(max))
+}
+
+pub fn set_progress_bar_action_named(name: &str, action: &str, color: Color, style: Style) {
+    report("set progress bar action", try_set_progress_bar_action_named(name, action, color, style));
+}
+
+pub fn try_set_progress_bar_action_named(name: &str, action: &str, color: Color, style: Style) -> Result<(), ProgressBarError> {
+    named_progress_bar(name).map(|progress_bar| progress_bar.set_action(action, color, style))
+}
+
+pub fn set_progress_bar_message_named(name: &str, message: &str) {
+    report("set progress bar message", try_set_progress_bar_message_named(name, message));
+}
+
+pub fn try_set_progress_bar_message_named(name: &str, message: &str) -> Result<(), ProgressBarError> {
+    named_progress_bar(name).map(|progress_bar| progress_bar.set_message(message))
+}
+
+pub fn print_progress_bar_info_named(name: &str, info_name: &str, text: &str, info_color: Color, info_style: Style) {
+    report("print progress bar info", try_print_progress_bar_info_named(name, info_name, text, info_color, info_style));
+}
+
+pub fn try_print_progress_bar_info_named(name: &str, info_name: &str, text: &str, info_color: Color, info_style: Style) -> Result<(), ProgressBarError> {
+    named_progress_bar(name).map(|progress_bar| progress_bar.print_info(info_name, text, info_color, info_style))
+}
+
+pub fn finalize_progress_bar_named(name: &str) {
+    report("finalize progress bar", try_finalize_progress_bar_named(name));
+}
+
+pub fn try_finalize_progress_bar_named(name: &str) -> Result<(), ProgressBarError> {
+    let progress_bar = REGISTRY.lock().map_err(|_| ProgressBarError::Poisoned)?.progress_bars.remove(name);
+    progress_bar.ok_or_else(|| ProgressBarError::NoProgressBarNamed(name.to_string()))?.finalize();
+    Ok(())
 }
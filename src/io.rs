@@ -0,0 +1,138 @@
+//! Readers and writers advancing a bar by the number of bytes transferred.
+//!
+//! # Example
+//!
+//! ```
+//! use progress_bar::{pb::ProgressBar, Units};
+//! use std::io::{self, Cursor};
+//!
+//! let data = vec![0u8; 1 << 20];
+//! let mut progress_bar = ProgressBar::with_writer(data.len(), io::stderr());
+//! progress_bar.set_units(Units::BinaryBytes);
+//!
+//! let mut reader = progress_bar.wrap_read(Cursor::new(data));
+//! io::copy(&mut reader, &mut io::sink()).unwrap();
+//! reader.progress_bar().finalize();
+//! ```
+
+use crate::pb::ProgressBar;
+use std::io::{self, BufRead, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+
+impl ProgressBar {
+    /// Wrap a reader, so that the bar advances by the number of bytes read.
+    /// Seeking sets the progress to the new position.
+    pub fn wrap_read<R>(self, reader: R) -> ProgressReader<R> {
+        ProgressReader { inner: reader, progress_bar: self }
+    }
+
+    /// Wrap a writer, so that the bar advances by the number of bytes written.
+    /// Seeking sets the progress to the new position.
+    pub fn wrap_write<W>(self, writer: W) -> ProgressWriter<W> {
+        ProgressWriter { inner: writer, progress_bar: self }
+    }
+}
+
+/// A reader advancing its bar, created with [ProgressBar::wrap_read]
+pub struct ProgressReader<R> {
+    pub(crate) inner: R,
+    pub(crate) progress_bar: ProgressBar,
+}
+
+impl<R> ProgressReader<R> {
+    /// Get the bar, to finalize it for instance
+    pub fn progress_bar(&mut self) -> &mut ProgressBar {
+        &mut self.progress_bar
+    }
+
+    /// Get the wrapped reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get back the reader and the bar
+    pub fn into_inner(self) -> (R, ProgressBar) {
+        (self.inner, self.progress_bar)
+    }
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.progress_bar.inc_by(read as u64);
+        Ok(read)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        let read = self.inner.read_vectored(bufs)?;
+        self.progress_bar.inc_by(read as u64);
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for ProgressReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.progress_bar.inc_by(amt as u64);
+    }
+}
+
+impl<R: Seek> Seek for ProgressReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = self.inner.seek(pos)?;
+        self.progress_bar.set_position(position);
+        Ok(position)
+    }
+}
+
+/// A writer advancing its bar, created with [ProgressBar::wrap_write]
+pub struct ProgressWriter<W> {
+    pub(crate) inner: W,
+    pub(crate) progress_bar: ProgressBar,
+}
+
+impl<W> ProgressWriter<W> {
+    /// Get the bar, to finalize it for instance
+    pub fn progress_bar(&mut self) -> &mut ProgressBar {
+        &mut self.progress_bar
+    }
+
+    /// Get the wrapped writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get back the writer and the bar
+    pub fn into_inner(self) -> (W, ProgressBar) {
+        (self.inner, self.progress_bar)
+    }
+}
+
+impl<W: Write> Write for ProgressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.progress_bar.inc_by(written as u64);
+        Ok(written)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let written = self.inner.write_vectored(bufs)?;
+        self.progress_bar.inc_by(written as u64);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek> Seek for ProgressWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = self.inner.seek(pos)?;
+        self.progress_bar.set_position(position);
+        Ok(position)
+    }
+}
@@ -0,0 +1,89 @@
+//! Progress bars driven by iterators.
+//!
+//! # Example
+//!
+//! ```
+//! use progress_bar::iter::ProgressIterator;
+//!
+//! let mut sum = 0;
+//! for i in (0..100).progress() {
+//!     sum += i;
+//! }
+//! ```
+
+use crate::pb::ProgressBar;
+
+/// Wraps iterators into [ProgressBarIter], which increments a bar on each item
+pub trait ProgressIterator: Iterator + Sized {
+    /// Wrap the iterator in a new bar on stdout.
+    /// The maximum is the length of the iterator when known exactly, otherwise the bar is indeterminate.
+    fn progress(self) -> ProgressBarIter<Self> {
+        let progress_bar = match self.size_hint() {
+            (lower, Some(upper)) if lower == upper => ProgressBar::new(upper),
+            _ => ProgressBar::new_indeterminate(),
+        };
+        self.progress_with(progress_bar)
+    }
+
+    /// Wrap the iterator in `progress_bar`, which can be configured beforehand
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use progress_bar::{iter::ProgressIterator, pb::ProgressBar, Color, Style};
+    ///
+    /// let mut progress_bar = ProgressBar::with_writer(3, std::io::stderr());
+    /// progress_bar.set_action("Counting", Color::Blue, Style::Bold);
+    /// let words: Vec<_> = ["a", "b", "c"].into_iter().progress_with(progress_bar).collect();
+    /// ```
+    fn progress_with(self, progress_bar: ProgressBar) -> ProgressBarIter<Self> {
+        ProgressBarIter { iter: self, progress_bar, finished: false }
+    }
+}
+
+impl<I: Iterator> ProgressIterator for I {}
+
+/// An iterator incrementing its bar each time an item is yielded.
+/// The bar is finalized when the iterator is exhausted, or when it is dropped if it was displayed (see [ProgressBar]'s `Drop`).
+pub struct ProgressBarIter<I> {
+    pub(crate) iter: I,
+    pub(crate) progress_bar: ProgressBar,
+    pub(crate) finished: bool,
+}
+
+impl<I> ProgressBarIter<I> {
+    /// Get the bar, to set a message for instance
+    pub fn progress_bar(&mut self) -> &mut ProgressBar {
+        &mut self.progress_bar
+    }
+
+    pub(crate) fn finish(&mut self) {
+        if !self.finished {
+            self.finished = true;
+            self.progress_bar.finalize();
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for ProgressBarIter<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        match self.iter.next() {
+            Some(item) => {
+                self.progress_bar.inc();
+                Some(item)
+            }
+            None => {
+                self.finish();
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for ProgressBarIter<I> {}
@@ -3,12 +3,32 @@
 pub mod pb;
 pub mod style;
 pub mod global;
+pub mod template;
+pub mod spinner;
+pub mod multi;
+pub mod ticker;
+pub mod estimator;
+pub mod iter;
+pub mod io;
+pub mod shared;
+mod format;
+mod output;
+mod term;
 
 #[cfg(feature = "logger")]
 pub(crate) mod logger;
+#[cfg(feature = "tokio")]
+pub mod asynchronous;
+#[cfg(feature = "rayon")]
+pub mod parallel;
 
 pub use style::*;
 pub use global::*;
+pub use iter::ProgressIterator;
 
 #[cfg(feature = "logger")]
 pub use logger::*;
+#[cfg(feature = "tokio")]
+pub use asynchronous::*;
+#[cfg(feature = "rayon")]
+pub use parallel::ParallelProgressIterator;
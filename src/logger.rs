@@ -1,5 +1,5 @@
 use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
-use crate::{Color, Style, CURRENT_PROGRESS_BAR};
+use crate::{global::named_progress_bars, Color, Style, CURRENT_PROGRESS_BAR};
 
 struct StdoutLogger;
 
@@ -22,6 +22,7 @@ pub enum InnerLogger {
     /// A main logger will always be used.
     /// 
     /// When a progress bar is active, the provided logger will be used. The progress bar will always be properly updated after each log.
+    /// Named progress bars (see [`init_progress_bar_named`](crate::init_progress_bar_named)) count as active too.
     /// When there is no progress bar, the provided logger will be used.
     Main(&'static dyn Log),
 
@@ -62,44 +63,65 @@ impl<L: Log + 'static> From<L> for InnerLogger {
 
 struct ProgressBarLogger(InnerLogger);
 
+/// How a log line is labeled when printed above progress bars
+fn label(level: Level) -> (&'static str, Color, Style) {
+    match level {
+        Level::Error => ("Error", Color::Red, Style::Bold),
+        Level::Warn => ("Warn", Color::Yellow, Style::Bold),
+        Level::Info => ("Info", Color::LightGreen, Style::Bold),
+        Level::Debug => ("Debug", Color::Blue, Style::Normal),
+        Level::Trace => ("Trace", Color::LightGray, Style::Normal),
+    }
+}
+
+/// Whether a progress bar is displayed, either the global one or named ones
+fn progress_bar_active() -> bool {
+    matches!(CURRENT_PROGRESS_BAR.lock().as_deref(), Ok(Some(_))) || named_progress_bars().is_some()
+}
+
 impl log::Log for ProgressBarLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
         match self.0 {
             InnerLogger::Main(inner) => inner.enabled(metadata),
-            InnerLogger::Fallback(inner) => match CURRENT_PROGRESS_BAR.lock().as_deref() {
-                Ok(Some(_)) => true,
-                Ok(None) | Err(_) => inner.enabled(metadata),
-            }
-            InnerLogger::None => matches!(CURRENT_PROGRESS_BAR.lock().as_deref(), Ok(Some(_))),
+            InnerLogger::Fallback(inner) => progress_bar_active() || inner.enabled(metadata),
+            InnerLogger::None => progress_bar_active(),
         }
     }
 
     fn log(&self, record: &Record) {
         match self.0 {
-            InnerLogger::Main(inner) => {
-                print!("\r\x1B[K\r");
-                eprint!("\r\x1B[K\r");
-                inner.log(record);
-                if let Ok(Some(progress_bar)) = CURRENT_PROGRESS_BAR.lock().as_deref() {
+            InnerLogger::Main(inner) => match CURRENT_PROGRESS_BAR.lock().as_deref() {
+                Ok(Some(progress_bar)) => {
+                    progress_bar.clear();
+                    inner.log(record);
                     progress_bar.display();
                 }
-            }
-            inner => match CURRENT_PROGRESS_BAR.lock().as_deref_mut() {
-                Ok(Some(progress_bar)) => match record.level() {
-                    Level::Error => progress_bar.print_info("Error", &record.args().to_string(), Color::Red, Style::Bold),
-                    Level::Warn => progress_bar.print_info("Warn", &record.args().to_string(), Color::Yellow, Style::Bold),
-                    Level::Info => progress_bar.print_info("Info", &record.args().to_string(), Color::LightGreen, Style::Bold),
-                    Level::Debug => progress_bar.print_info("Debug", &record.args().to_string(), Color::Blue, Style::Normal),
-                    Level::Trace => progress_bar.print_info("Trace", &record.args().to_string(), Color::LightGray, Style::Normal),
+                Ok(None) | Err(_) => match named_progress_bars() {
+                    Some(multi) => multi.suspend(|| inner.log(record)),
+                    None => {
+                        print!("\r\x1B[K\r");
+                        eprint!("\r\x1B[K\r");
+                        inner.log(record);
+                    }
                 },
-                Ok(None) | Err(_) => match inner {
-                    InnerLogger::Main(_) => unreachable!(),
-                    InnerLogger::Fallback(inner) => {
+            },
+            inner => match CURRENT_PROGRESS_BAR.lock().as_deref_mut() {
+                Ok(Some(progress_bar)) => {
+                    let (name, color, style) = label(record.level());
+                    progress_bar.print_info(name, &record.args().to_string(), color, style);
+                }
+                Ok(None) | Err(_) => match (named_progress_bars(), inner) {
+                    (Some(multi), _) => {
+                        let (name, color, style) = label(record.level());
+                        multi.print_info(name, &record.args().to_string(), color, style);
+                    }
+                    (None, InnerLogger::Main(_)) => unreachable!(),
+                    (None, InnerLogger::Fallback(inner)) => {
                         print!("\r\x1B[K\r");
                         eprint!("\r\x1B[K\r");
                         inner.log(record)
                     },
-                    InnerLogger::None => (),
+                    (None, InnerLogger::None) => (),
                 },
             },
         }
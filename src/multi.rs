@@ -0,0 +1,501 @@
+//! Several progress bars displayed at once.
+
+use crate::output::Output;
+use crate::pb::ProgressBar;
+use crate::style::{Color, Style};
+use crate::ticker::{self, Ticker};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+struct Entry {
+    id: usize,
+    progress_bar: ProgressBar,
+    /// Progress and time of the last status line printed in plain mode
+    last_report: Option<(u64, Instant)>,
+    parent: Option<usize>,
+    /// Weight in the progress of the parent, defaults to the maximum progress of the bar
+    weight: Option<u64>,
+    /// How the progress of children is aggregated, if this bar has any
+    aggregation: Aggregation,
+    /// Finalized children stay in the block to count in the progress of their parent, but are hidden
+    finished: bool,
+}
+
+/// How the progress of a parent bar is derived from its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Aggregation {
+    /// The parent counts finished children
+    Count,
+    /// The parent sums the progress of children, each child counting as much as its weight.
+    /// By default, the weight of a child is its maximum progress.
+    #[default]
+    Weight,
+}
+
+impl Entry {
+    fn is_finished(&self) -> bool {
+        self.finished || self.progress_bar.length().is_some_and(|max| self.progress_bar.position() >= max)
+    }
+
+    /// The weighted progress of this child, and its weight
+    fn weighted_progress(&self) -> (u64, u64) {
+        let max = self.progress_bar.length();
+        let weight = self.weight.or(max).unwrap_or(1);
+        match max {
+            _ if self.is_finished() => (weight, weight),
+            Some(max) if max > 0 => ((weight as u128 * self.progress_bar.position() as u128 / max as u128) as u64, weight),
+            _ => (0, weight),
+        }
+    }
+}
+
+struct MultiState {
+    output: Output,
+    entries: Vec<Entry>,
+    next_id: usize,
+    /// Minimum time between two redraws caused by progress
+    redraw_interval: Option<Duration>,
+    next_redraw: Instant,
+}
+
+/// A block of progress bars stacked on consecutive lines.  
+/// Each bar is updated through its own [MultiProgressBar] handle, which can be sent to another thread.
+/// 
+/// # Example
+/// 
+/// ```
+/// use progress_bar::{multi::MultiProgress, Color, Style};
+/// use std::thread;
+/// 
+/// let multi = MultiProgress::new();
+/// let handles: Vec<_> = (0..3).map(|i| {
+///     let progress_bar = multi.add(50);
+///     progress_bar.set_action(&format!("Download {i}"), Color::Blue, Style::Bold);
+///     thread::spawn(move || {
+///         for _ in 0..50 {
+///             progress_bar.inc();
+///         }
+///         progress_bar.finalize();
+///     })
+/// }).collect();
+/// 
+/// multi.print_info("Started", "3 downloads", Color::LightGreen, Style::Bold);
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct MultiProgress {
+    state: Arc<Mutex<MultiState>>,
+}
+
+/// A handle to a progress bar of a [MultiProgress].  
+/// Handles can be cloned and sent to other threads; updates redraw the whole block.
+#[derive(Clone)]
+pub struct MultiProgressBar {
+    id: usize,
+    state: Arc<Mutex<MultiState>>,
+}
+
+fn lock(state: &Mutex<MultiState>) -> MutexGuard<'_, MultiState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn insert(state: &Arc<Mutex<MultiState>>, max: Option<u64>, parent: Option<usize>, weight: Option<u64>) -> MultiProgressBar {
+    let mut progress_bar = ProgressBar::with_writer(0, io::sink());
+    match max {
+        Some(max) => progress_bar.set_length(max),
+        None => progress_bar.clear_max(),
+    }
+    let mut guard = lock(state);
+    let parent = parent.filter(|&parent| guard.entry(parent).is_some());
+    let id = guard.next_id;
+    guard.next_id += 1;
+    guard.entries.push(Entry {
+        id,
+        progress_bar,
+        last_report: None,
+        parent,
+        weight,
+        aggregation: Aggregation::default(),
+        finished: false,
+    });
+    #[allow(unused_must_use)]
+    { guard.draw(); }
+    MultiProgressBar { id, state: Arc::clone(state) }
+}
+
+impl MultiState {
+    fn entry(&mut self, id: usize) -> Option<&mut Entry> {
+        self.entries.iter_mut().find(|entry| entry.id == id)
+    }
+
+    /// Derive the progress of parents from their children.
+    /// Children are always inserted after their parent, so walking backwards updates children before their parent.
+    fn aggregate(&mut self) {
+        for index in (0..self.entries.len()).rev() {
+            let id = self.entries[index].id;
+            let children = self.entries.iter().filter(|entry| entry.parent == Some(id));
+            let (progress, max) = match self.entries[index].aggregation {
+                Aggregation::Count => children.fold((0, 0), |(progress, max), child| (progress + child.is_finished() as u64, max + 1)),
+                Aggregation::Weight => children.fold((0u64, 0u64), |(progress, max), child| {
+                    let (child_progress, weight) = child.weighted_progress();
+                    (progress.saturating_add(child_progress), max.saturating_add(weight))
+                }),
+            };
+            if self.entries.iter().any(|entry| entry.parent == Some(id)) {
+                self.entries[index].progress_bar.aggregate(progress, max);
+            }
+        }
+    }
+
+    /// Indexes of the entries to display in order, with their depth.
+    /// Children are displayed under their parent, unless they are finished.
+    fn visible(&self) -> Vec<(usize, usize)> {
+        fn visit(entries: &[Entry], parent: Option<usize>, depth: usize, visible: &mut Vec<(usize, usize)>) {
+            for (index, entry) in entries.iter().enumerate() {
+                if entry.parent == parent && !(parent.is_some() && entry.is_finished()) {
+                    visible.push((index, depth));
+                    visit(entries, Some(entry.id), depth + 1, visible);
+                }
+            }
+        }
+        let mut visible = Vec::new();
+        visit(&self.entries, None, 0, &mut visible);
+        visible
+    }
+
+    /// Ids of a bar and all its descendants
+    fn subtree(&self, id: usize) -> Vec<usize> {
+        let mut ids = vec![id];
+        let mut index = 0;
+        while let Some(&parent) = ids.get(index) {
+            ids.extend(self.entries.iter().filter(|entry| entry.parent == Some(parent)).map(|entry| entry.id));
+            index += 1;
+        }
+        ids
+    }
+
+    /// Redraw all bars, unless they were redrawn less than the redraw interval ago and `force` is false
+    fn redraw(&mut self, force: bool) -> io::Result<()> {
+        if let Some(interval) = self.redraw_interval {
+            let now = Instant::now();
+            if now < self.next_redraw && !force {
+                return Ok(());
+            }
+            self.next_redraw = now + interval;
+        }
+        self.draw()
+    }
+
+    /// Redraw all bars.
+    /// The cursor is left at the beginning of the first line of the block.
+    fn draw(&mut self) -> io::Result<()> {
+        self.aggregate();
+        if !self.output.terminal {
+            return self.report();
+        }
+        let columns = self.output.columns();
+        let visible = self.visible();
+        let mut frame = String::from("\r");
+        for &(index, depth) in &visible {
+            let columns = columns.map(|columns| columns.saturating_sub(2 * depth));
+            frame.push_str(&"  ".repeat(depth));
+            frame.push_str(&self.entries[index].progress_bar.render_frame(true, columns));
+            frame.push_str("\x1B[K\n");
+        }
+        frame.push_str("\x1B[J");
+        if !visible.is_empty() {
+            frame.push_str(&format!("\x1B[{}A", visible.len()));
+        }
+        self.output.writer.write_all(frame.as_bytes())?;
+        self.output.writer.flush()
+    }
+
+    /// Print status lines for the bars that made enough progress, in plain mode
+    fn report(&mut self) -> io::Result<()> {
+        let columns = self.output.columns();
+        for (index, depth) in self.visible() {
+            let entry = &mut self.entries[index];
+            let (progress, max) = (entry.progress_bar.position(), entry.progress_bar.length());
+            if self.output.report_due(entry.last_report, progress, max) {
+                entry.last_report = Some((progress, Instant::now()));
+                writeln!(self.output.writer, "{}{}", "  ".repeat(depth), entry.progress_bar.render_frame(false, columns))?;
+            }
+        }
+        self.output.writer.flush()
+    }
+
+    /// Print a line above the block
+    fn print_info(&mut self, info_name: &str, text: &str, info_color: Color, info_style: Style) -> io::Result<()> {
+        if self.output.terminal {
+            write!(self.output.writer, "\r")?;
+        }
+        self.output.print_info(info_name, text, info_color, info_style)?;
+        self.draw()
+    }
+
+    /// Take a bar out of the block, leaving its last state above the block.
+    /// Children are collapsed under their parent instead.
+    fn finalize(&mut self, id: usize) -> io::Result<()> {
+        let Some(index) = self.entries.iter().position(|entry| entry.id == id) else {
+            return Ok(());
+        };
+        if self.entries[index].parent.is_some() {
+            self.entries[index].finished = true;
+            self.entries[index].progress_bar.finish();
+            return self.draw();
+        }
+        self.aggregate();
+        let mut entry = self.entries.remove(index);
+        entry.progress_bar.finish();
+        self.remove(id);
+        let columns = self.output.columns();
+        if self.output.terminal {
+            write!(self.output.writer, "\r{}\x1B[K\n", entry.progress_bar.render_frame(true, columns))?;
+        } else {
+            writeln!(self.output.writer, "{}", entry.progress_bar.render_frame(false, columns))?;
+        }
+        self.draw()
+    }
+
+    /// Advance spinners and redraw the block, if it is not empty
+    fn steady_tick(&mut self) -> io::Result<()> {
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+        for entry in &mut self.entries {
+            entry.progress_bar.spin();
+        }
+        self.draw()
+    }
+
+    /// Remove a bar and all its descendants
+    fn remove(&mut self, id: usize) {
+        let ids = self.subtree(id);
+        self.entries.retain(|entry| !ids.contains(&entry.id));
+    }
+}
+
+impl MultiProgress {
+    /// Creates an empty block of progress bars, displayed on stdout
+    pub fn new() -> Self {
+        MultiProgress::with_writer(io::stdout())
+    }
+
+    /// Same as [MultiProgress::new] but the bars and logs are written to `writer` instead of stdout
+    pub fn with_writer<W: Write + Send + 'static>(writer: W) -> Self {
+        MultiProgress {
+            state: Arc::new(Mutex::new(MultiState {
+                output: Output::new(writer),
+                entries: Vec::new(),
+                next_id: 0,
+                redraw_interval: None,
+                next_redraw: Instant::now(),
+            })),
+        }
+    }
+
+    /// Add a progress bar at the bottom of the block, see [ProgressBar::new]
+    pub fn add(&self, max: usize) -> MultiProgressBar {
+        insert(&self.state, Some(max as u64), None, None)
+    }
+
+    /// Add a progress bar for an unknown number of actions at the bottom of the block, see [ProgressBar::new_indeterminate]
+    pub fn add_indeterminate(&self) -> MultiProgressBar {
+        insert(&self.state, None, None, None)
+    }
+
+    /// Log something above the block
+    pub fn print_info(&self, info_name: &str, text: &str, info_color: Color, info_style: Style) {
+        #[allow(unused_must_use)]
+        { lock(&self.state).print_info(&ProgressBar::set_good_size(info_name), text, info_color, info_style); }
+    }
+
+    /// Clear the block, let `f` print something in its place, then redraw the block below
+    #[cfg(feature = "logger")]
+    pub(crate) fn suspend(&self, f: impl FnOnce()) {
+        let mut state = lock(&self.state);
+        #[allow(unused_must_use)]
+        {
+            if state.output.terminal {
+                write!(state.output.writer, "\r\x1B[J");
+                state.output.writer.flush();
+            }
+            f();
+            state.draw();
+        }
+    }
+
+    /// Limit how often the block is redrawn when progress is made, see [ProgressBar::set_max_redraw_rate]
+    pub fn set_max_redraw_rate(&self, per_second: Option<u32>) {
+        lock(&self.state).redraw_interval = per_second.filter(|&per_second| per_second > 0).map(|per_second| Duration::from_secs(1) / per_second);
+    }
+
+    /// Redraw the block every `interval` in a background thread, see [Ticker].  
+    /// The thread stops when the ticker is dropped, or when the block and all its bars are dropped.
+    pub fn spawn_ticker(&self, interval: Duration) -> Ticker {
+        let state = Arc::downgrade(&self.state);
+        Ticker::spawn(interval, move || match state.upgrade() {
+            Some(state) => {
+                if let Some(mut state) = ticker::try_lock(&state) {
+                    #[allow(unused_must_use)]
+                    { state.steady_tick(); }
+                }
+                true
+            }
+            None => false,
+        })
+    }
+
+    /// Force plain mode on or off, see [ProgressBar::set_plain_mode]
+    pub fn set_plain_mode(&self, plain: bool) {
+        lock(&self.state).output.terminal = !plain;
+    }
+
+    /// Override the detected width of the terminal, see [ProgressBar::set_terminal_width]
+    pub fn set_terminal_width(&self, columns: usize) {
+        let mut state = lock(&self.state);
+        state.output.forced_columns = Some(columns);
+        #[allow(unused_must_use)]
+        { state.draw(); }
+    }
+}
+
+impl Default for MultiProgress {
+    fn default() -> Self {
+        MultiProgress::new()
+    }
+}
+
+impl MultiProgressBar {
+    /// Access the underlying progress bar, then redraw the block.  
+    /// Returns `None` if the bar was finalized or removed.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::{multi::MultiProgress, spinner::{Indeterminate, Spinner}};
+    /// 
+    /// let multi = MultiProgress::with_writer(std::io::sink());
+    /// let progress_bar = multi.add_indeterminate();
+    /// progress_bar.update(|progress_bar| progress_bar.set_indeterminate(Indeterminate::Spinner(Spinner::dots())));
+    /// ```
+    pub fn update<R>(&self, f: impl FnOnce(&mut ProgressBar) -> R) -> Option<R> {
+        let mut state = lock(&self.state);
+        let entry = state.entry(self.id)?;
+        let result = f(&mut entry.progress_bar);
+        let complete = entry.is_finished();
+        #[allow(unused_must_use)]
+        { state.redraw(complete); }
+        Some(result)
+    }
+
+    /// Increment the progress by 1
+    pub fn inc(&self) {
+        self.update(ProgressBar::inc);
+    }
+
+    /// Advance spinners without making progress
+    pub fn tick(&self) {
+        self.update(ProgressBar::tick);
+    }
+
+    /// Increment the progress by `n`, like the number of bytes just copied
+    pub fn inc_by(&self, n: u64) {
+        self.update(|progress_bar| progress_bar.inc_by(n));
+    }
+
+    /// Set the progress
+    pub fn set_progress(&self, progress: usize) {
+        self.update(|progress_bar| progress_bar.set_progress(progress));
+    }
+
+    /// Same as [MultiProgressBar::set_progress] with a `u64`, see [ProgressBar::set_position]
+    pub fn set_position(&self, position: u64) {
+        self.update(|progress_bar| progress_bar.set_position(position));
+    }
+
+    /// Set the maximum progress
+    pub fn set_max(&self, max: usize) {
+        self.update(|progress_bar| progress_bar.set_max(max));
+    }
+
+    /// Same as [MultiProgressBar::set_max] with a `u64`, see [ProgressBar::set_position]
+    pub fn set_length(&self, max: u64) {
+        self.update(|progress_bar| progress_bar.set_length(max));
+    }
+
+    /// Set the action displayed before the progress bar
+    pub fn set_action(&self, action: &str, color: Color, style: Style) {
+        self.update(|progress_bar| progress_bar.set_action(action, color, style));
+    }
+
+    /// Set the message displayed at the end of the line, see [ProgressBar::set_message]
+    pub fn set_message(&self, message: &str) {
+        self.update(|progress_bar| progress_bar.set_message(message));
+    }
+
+    /// Log something above the block
+    pub fn print_info(&self, info_name: &str, text: &str, info_color: Color, info_style: Style) {
+        #[allow(unused_must_use)]
+        { lock(&self.state).print_info(&ProgressBar::set_good_size(info_name), text, info_color, info_style); }
+    }
+
+    /// Attach a child bar, displayed under this one.  
+    /// The progress of this bar is then derived from its children (see [Aggregation]).
+    /// If this bar was finalized or removed, the child is added at the bottom of the block instead.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::{multi::MultiProgress, Color, Style};
+    /// 
+    /// let multi = MultiProgress::new();
+    /// let build = multi.add(0);
+    /// build.set_action("Building", Color::Blue, Style::Bold);
+    /// let crates: Vec<_> = (0..3).map(|_| build.add_child(10)).collect();
+    /// for child in crates {
+    ///     for _ in 0..10 {
+    ///         child.inc();
+    ///     }
+    ///     child.finalize();
+    /// }
+    /// build.finalize();
+    /// ```
+    pub fn add_child(&self, max: usize) -> MultiProgressBar {
+        insert(&self.state, Some(max as u64), Some(self.id), None)
+    }
+
+    /// Same as [MultiProgressBar::add_child], but the child counts as much as `weight` in the progress of this bar
+    /// when using [Aggregation::Weight]
+    pub fn add_weighted_child(&self, max: usize, weight: usize) -> MultiProgressBar {
+        insert(&self.state, Some(max as u64), Some(self.id), Some(weight as u64))
+    }
+
+    /// Set how the progress of this bar is derived from its children (default: [Aggregation::Weight])
+    pub fn set_aggregation(&self, aggregation: Aggregation) {
+        let mut state = lock(&self.state);
+        if let Some(entry) = state.entry(self.id) {
+            entry.aggregation = aggregation;
+        }
+        #[allow(unused_must_use)]
+        { state.draw(); }
+    }
+
+    /// Take the bar out of the block, leaving its last state above the other bars.  
+    /// Children are collapsed under their parent instead, and keep counting in its progress.
+    pub fn finalize(&self) {
+        #[allow(unused_must_use)]
+        { lock(&self.state).finalize(self.id); }
+    }
+
+    /// Take the bar and its children out of the block without leaving any trace
+    pub fn remove(&self) {
+        let mut state = lock(&self.state);
+        state.remove(self.id);
+        #[allow(unused_must_use)]
+        { state.draw(); }
+    }
+}
@@ -0,0 +1,129 @@
+//! The sink progress bars are written to.
+
+use crate::style::{Color, Style};
+use crate::term;
+use std::any::Any;
+use std::fs::File;
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+#[cfg(unix)]
+use std::os::fd::{AsRawFd, RawFd};
+
+pub(crate) struct Output {
+    pub(crate) writer: Box<dyn Write + Send>,
+    /// When the writer is not a terminal, the bar is printed as plain status lines without any escape code
+    pub(crate) terminal: bool,
+    /// File descriptor of the terminal the writer is attached to, used to query its size
+    #[cfg(unix)]
+    tty: Option<RawFd>,
+    /// Width of the line set by the user, overriding the size of the terminal
+    pub(crate) forced_columns: Option<usize>,
+    /// Last known size of the terminal, along with the resize generation it was queried at
+    #[cfg(unix)]
+    columns: Option<(usize, Option<usize>)>,
+    /// Minimum progress, in percent, between two status lines in plain mode
+    pub(crate) report_step: usize,
+    /// Maximum time between two status lines in plain mode, if progress was made
+    pub(crate) report_interval: Duration,
+    /// Progress and time of the last status line printed in plain mode
+    pub(crate) last_report: Option<(u64, Instant)>,
+    /// Whether the bar was displayed since it was last finalized
+    pub(crate) drawn: bool,
+}
+
+/// Whether the writer is a terminal.
+/// Only standard streams and files can be detected; other writers are assumed not to be terminals.
+fn is_terminal(writer: &dyn Any) -> bool {
+    if let Some(stdout) = writer.downcast_ref::<io::Stdout>() {
+        stdout.is_terminal()
+    } else if let Some(stderr) = writer.downcast_ref::<io::Stderr>() {
+        stderr.is_terminal()
+    } else if let Some(file) = writer.downcast_ref::<File>() {
+        file.is_terminal()
+    } else {
+        false
+    }
+}
+
+/// File descriptor of the writer, if it is a terminal
+#[cfg(unix)]
+fn tty(writer: &dyn Any) -> Option<RawFd> {
+    if let Some(stdout) = writer.downcast_ref::<io::Stdout>() {
+        stdout.is_terminal().then(|| stdout.as_raw_fd())
+    } else if let Some(stderr) = writer.downcast_ref::<io::Stderr>() {
+        stderr.is_terminal().then(|| stderr.as_raw_fd())
+    } else if let Some(file) = writer.downcast_ref::<File>() {
+        file.is_terminal().then(|| file.as_raw_fd())
+    } else {
+        None
+    }
+}
+
+impl Output {
+    pub(crate) fn new<W: Write + Send + 'static>(writer: W) -> Output {
+        Output {
+            terminal: is_terminal(&writer),
+            #[cfg(unix)]
+            tty: tty(&writer),
+            forced_columns: None,
+            #[cfg(unix)]
+            columns: None,
+            writer: Box::new(writer),
+            report_step: 10,
+            report_interval: Duration::from_secs(30),
+            last_report: None,
+            drawn: false,
+        }
+    }
+
+    /// Number of columns available for a line, if known.  
+    /// The size of the terminal is queried again each time it is resized.
+    pub(crate) fn columns(&mut self) -> Option<usize> {
+        if self.forced_columns.is_some() || !self.terminal {
+            return self.forced_columns;
+        }
+        #[cfg(unix)]
+        if let Some(fd) = self.tty {
+            let generation = term::resize_generation();
+            return match self.columns {
+                Some((queried_at, columns)) if queried_at == generation => columns,
+                _ => {
+                    let columns = term::columns(fd);
+                    self.columns = Some((generation, columns));
+                    columns
+                }
+            };
+        }
+        None
+    }
+
+    /// Print a log line, styled only if the writer is a terminal
+    pub(crate) fn print_info(&mut self, info_name: &str, text: &str, info_color: Color, info_style: Style) -> io::Result<()> {
+        if self.terminal {
+            writeln!(self.writer, "{}{}{}\x1B[0m {}\x1B[K", info_style, info_color, info_name, text)?;
+        } else {
+            writeln!(self.writer, "{} {}", info_name, text)?;
+        }
+        self.writer.flush()
+    }
+
+    /// Whether a new status line should be printed in plain mode.  
+    /// When `max` is unknown, or once it is reached, status lines are only printed at regular intervals.
+    pub(crate) fn report_due(&self, last_report: Option<(u64, Instant)>, progress: u64, max: Option<u64>) -> bool {
+        let percent = |progress: u64| match max {
+            Some(0) => 100,
+            Some(max) => (progress.min(max) as u128 * 100 / max as u128) as usize,
+            None => 0,
+        };
+        match last_report {
+            None => true,
+            Some((last, _)) if last == progress => false,
+            Some((last, at)) => {
+                progress < last
+                    || max.is_some_and(|max| last < max && progress >= max)
+                    || (max.is_some() && percent(progress) >= percent(last) + self.report_step)
+                    || at.elapsed() >= self.report_interval
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Progress bars for rayon parallel iterators, enabled with the `rayon` feature.
+//!
+//! Workers count items through a [SharedProgressBar], so that parallel loops don't contend on the bar.
+//!
+//! # Example
+//!
+//! ```
+//! use progress_bar::parallel::ParallelProgressIterator;
+//! use rayon::prelude::*;
+//!
+//! let sum: u64 = (0..1_000_000u64).into_par_iter().progress().map(|i| i * 2).sum();
+//! ```
+
+use crate::pb::ProgressBar;
+use crate::shared::SharedProgressBar;
+use rayon::iter::plumbing::{Consumer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+
+/// Wraps parallel iterators into [ProgressBarParIter], which counts items without locking
+pub trait ParallelProgressIterator: ParallelIterator {
+    /// Wrap the iterator in a new bar on stdout.
+    /// The maximum is the length of the iterator when known, otherwise the bar is indeterminate.
+    fn progress(self) -> ProgressBarParIter<Self> {
+        let progress_bar = match self.opt_len() {
+            Some(len) => ProgressBar::new(len),
+            None => ProgressBar::new_indeterminate(),
+        };
+        self.progress_with(progress_bar)
+    }
+
+    /// Wrap the iterator in `progress_bar`, which can be configured beforehand
+    fn progress_with(self, progress_bar: ProgressBar) -> ProgressBarParIter<Self> {
+        ProgressBarParIter { base: self, progress_bar }
+    }
+}
+
+impl<I: ParallelIterator> ParallelProgressIterator for I {}
+
+/// A parallel iterator incrementing its bar each time an item is yielded.
+/// The bar is finalized when the iterator has been fully driven.
+pub struct ProgressBarParIter<I> {
+    base: I,
+    progress_bar: ProgressBar,
+}
+
+impl<I: ParallelIterator> ParallelIterator for ProgressBarParIter<I> {
+    type Item = I::Item;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        let progress_bar = SharedProgressBar::new(self.progress_bar);
+        let handle = progress_bar.clone();
+        let result = self.base
+            .map(move |item| {
+                handle.inc();
+                item
+            })
+            .drive_unindexed(consumer);
+        progress_bar.finalize();
+        result
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        self.base.opt_len()
+    }
+}
+
+impl<I: IndexedParallelIterator> IndexedParallelIterator for ProgressBarParIter<I> {
+    fn len(&self) -> usize {
+        self.base.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        let progress_bar = SharedProgressBar::new(self.progress_bar);
+        let handle = progress_bar.clone();
+        let result = self.base
+            .map(move |item| {
+                handle.inc();
+                item
+            })
+            .drive(consumer);
+        progress_bar.finalize();
+        result
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        let progress_bar = SharedProgressBar::new(self.progress_bar);
+        let handle = progress_bar.clone();
+        let result = self.base
+            .map(move |item| {
+                handle.inc();
+                item
+            })
+            .with_producer(callback);
+        progress_bar.finalize();
+        result
+    }
+}
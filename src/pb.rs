@@ -1,16 +1,53 @@
+use crate::estimator::{Estimator, Linear, SlidingWindow};
+use crate::format;
+use crate::output::Output;
+use crate::spinner::{Indeterminate, Spinner};
 use crate::style::*;
+use crate::template::{self, Key, Piece, Template};
+use crate::term;
 use std::io;
 use std::io::Write;
-use std::time::Instant;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 
 pub struct ProgressBar {
-    max: usize,
-    progress: usize,
-    width: usize,
-    action: String,
+    /// None when the total is unknown
+    max: Option<u64>,
+    progress: u64,
+    /// Number of updates, used to animate spinners
+    ticks: usize,
+    /// When progress was last reset, tracked whether ETA is enabled or not
+    started: Instant,
+    indeterminate: Indeterminate,
+    /// Minimum time between two redraws caused by progress
+    redraw_interval: Option<Duration>,
+    next_redraw: Instant,
+    /// Whether the bar was finalized and not updated since
+    finished: bool,
+    /// Whether the bar was finalized with [ProgressBar::abandon] and not updated since
+    abandoned: bool,
+    width: Option<usize>,
+    bar_mode: BarMode,
+    bar_glyphs: BarGlyphs,
+    units: Units,
+    action: Option<String>,
     action_color: Color,
     action_style: Style,
-    start: Option<Instant>,
+    /// Displayed after the bar, on a single line
+    message: Option<String>,
+    /// Whether the ETA is displayed
+    show_eta: bool,
+    estimator: Box<dyn Estimator>,
+    /// Whether the rate is displayed
+    show_rate: bool,
+    /// Whether the percentage is displayed
+    show_percent: bool,
+    /// Whether the elapsed time is displayed
+    show_elapsed: bool,
+    /// Smoothed rate of progress
+    throughput: SlidingWindow,
+    template: Option<Template>,
+    output: Mutex<Output>,
 }
 
 impl ProgressBar {
@@ -47,45 +84,183 @@ impl ProgressBar {
     /// // progress_bar.finalize();
     /// ```
     pub fn new(max: usize) -> Self {
+        ProgressBar::with_writer(max, io::stdout())
+    }
+
+    /// Same as [ProgressBar::new] but the bar and its logs are written to `writer` instead of stdout.  
+    /// This is useful to keep stdout clean for data while the bar renders on stderr, or to capture the output in a file or buffer.  
+    /// If `writer` is not a terminal, the bar is printed in plain mode (see [ProgressBar::set_plain_mode]).
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::pb::ProgressBar;
+    /// use std::io;
+    /// 
+    /// let mut progress_bar = ProgressBar::with_writer(10, io::stderr());
+    /// progress_bar.inc();
+    /// progress_bar.finalize();
+    /// ```
+    pub fn with_writer<W: Write + Send + 'static>(max: usize, writer: W) -> Self {
         ProgressBar {
-            max,
+            max: Some(max as u64),
             progress: 0,
-            width: 50,
-            action: String::new(),
+            ticks: 0,
+            started: Instant::now(),
+            indeterminate: Indeterminate::default(),
+            redraw_interval: None,
+            next_redraw: Instant::now(),
+            finished: false,
+            abandoned: false,
+            width: None,
+            bar_mode: BarMode::Classic,
+            bar_glyphs: BarGlyphs::default(),
+            units: Units::Items,
+            action: None,
             action_color: Color::Black,
             action_style: Style::Normal,
-            start: None,
+            message: None,
+            show_eta: false,
+            estimator: Box::new(Linear::new()),
+            show_rate: false,
+            show_percent: false,
+            show_elapsed: false,
+            throughput: SlidingWindow::new(Duration::from_secs(5)),
+            template: None,
+            output: Mutex::new(Output::new(writer)),
         }
     }
 
     /// Same as [ProgressBar::new] but enabled ETA display.
     pub fn new_with_eta(max: usize) -> Self {
-        ProgressBar {
-            start: Some(Instant::now()),
-            ..ProgressBar::new(max)
-        }
+        let mut progress_bar = ProgressBar::new(max);
+        progress_bar.show_eta = true;
+        progress_bar
     }
 
-    fn set_good_size(text: &str) -> String {
-        match text.len() {
-            12 => text.to_string(),
-            len if len > 12 => text[..12].to_string(),
-            _ => {
-                let mut text = text.to_string();
-                while text.len() < 12 {
-                    text.insert(0, ' ');
-                }
-                text
-            },
-        }
+    /// Creates a progress bar for an unknown number of actions.  
+    /// A spinner is displayed along with the progress, the elapsed time and the rate, until [ProgressBar::set_max] is called.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::pb::ProgressBar;
+    /// 
+    /// let mut progress_bar = ProgressBar::new_indeterminate();
+    /// for _ in 0..10 {
+    ///     progress_bar.inc();
+    /// }
+    /// progress_bar.finalize();
+    /// ```
+    pub fn new_indeterminate() -> Self {
+        let mut progress_bar = ProgressBar::new(0);
+        progress_bar.max = None;
+        progress_bar
+    }
+
+    fn output(&self) -> MutexGuard<'_, Output> {
+        self.output.lock().unwrap_or_else(PoisonError::into_inner)
     }
 
-    /// Set the width of the progress bar in caracters in console (default: 50)
+    /// Cut or right-align `text` to 12 columns, as displayed in the action column
+    pub(crate) fn set_good_size(text: &str) -> String {
+        let text = term::truncate(text, 12);
+        let padding = 12 - term::width(&text);
+        format!("{}{}", " ".repeat(padding), text)
+    }
+
+    /// Set the width of the progress bar in caracters in console.  
+    /// By default, the bar fills the width of the terminal, or is 50 caracters wide if the output is not a terminal.
+    /// The bar is shrunk if needed so that the whole line fits in the terminal.
     pub fn set_width(&mut self, w: usize) {
-        self.width = w;
+        self.width = Some(w);
         self.display();
     }
 
+    /// Set how the bar is drawn (default: [BarMode::Classic])
+    pub fn set_bar_mode(&mut self, mode: BarMode) {
+        self.bar_mode = mode;
+        self.display();
+    }
+
+    /// Set the strings the bar is drawn with (default: `[=====>    ]`).  
+    /// In [BarMode::Smooth], only the brackets and the empty glyph are used.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::{pb::ProgressBar, BarGlyphs};
+    /// 
+    /// let mut progress_bar = ProgressBar::with_writer(4, std::io::sink());
+    /// progress_bar.set_width(8);
+    /// progress_bar.set_bar_glyphs(BarGlyphs::new("━", "╸", " ", "", ""));
+    /// progress_bar.inc();
+    /// assert_eq!(progress_bar.render_plain(), " ━╸       1/4");
+    /// ```
+    pub fn set_bar_glyphs(&mut self, glyphs: BarGlyphs) {
+        self.bar_glyphs = glyphs;
+        self.display();
+    }
+
+    /// Override the detected width of the terminal, in columns.  
+    /// The whole line is kept within this width, which also applies when the output is not a terminal.
+    pub fn set_terminal_width(&mut self, columns: usize) {
+        self.output().forced_columns = Some(columns);
+        self.display();
+    }
+
+    /// Set the layout of the progress line (default: the action, the bar, the progress counter and the ETA).  
+    /// See the [template](crate::template) module for the syntax.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::{pb::ProgressBar, template::Template};
+    /// 
+    /// let mut progress_bar = ProgressBar::new(100);
+    /// progress_bar.set_template(Template::parse("{bar:40} {percent:>3}% eta {eta}").unwrap());
+    /// ```
+    pub fn set_template(&mut self, template: Template) {
+        self.template = Some(template);
+        self.display();
+    }
+
+    /// Force plain mode on or off (default: on when the output is not a terminal).  
+    /// In plain mode, no escape code is ever printed and the bar is only printed as a new status line at meaningful milestones,
+    /// which keeps CI logs and redirected output readable.
+    pub fn set_plain_mode(&mut self, plain: bool) {
+        self.output().terminal = !plain;
+    }
+
+    /// Set how often status lines are printed in plain mode: each time progress advances by `percent` percents,
+    /// or after `interval` if some progress was made (default: 10% or 30 seconds)
+    pub fn set_plain_mode_interval(&mut self, percent: usize, interval: Duration) {
+        let mut output = self.output();
+        output.report_step = percent;
+        output.report_interval = interval;
+    }
+
+    /// Get the current progress, saturated to `usize::MAX` on 32-bit targets (see [ProgressBar::position])
+    pub fn progress(&self) -> usize {
+        usize::try_from(self.progress).unwrap_or(usize::MAX)
+    }
+
+    /// Get the maximum progress, or `None` in indeterminate mode.
+    /// It is saturated to `usize::MAX` on 32-bit targets (see [ProgressBar::length]).
+    pub fn max(&self) -> Option<usize> {
+        self.max.map(|max| usize::try_from(max).unwrap_or(usize::MAX))
+    }
+
+    /// Same as [ProgressBar::progress], as a `u64`
+    pub fn position(&self) -> u64 {
+        self.progress
+    }
+
+    /// Same as [ProgressBar::max], as a `u64`
+    pub fn length(&self) -> Option<u64> {
+        self.max
+    }
+
     #[deprecated(note = "Use set_progress instead")]
     pub fn set_progression(&mut self, p: usize) {
         self.set_progress(p)
@@ -93,110 +268,732 @@ impl ProgressBar {
 
     /// Set the progres
     pub fn set_progress(&mut self, p: usize) {
+        self.set_position(p as u64)
+    }
+
+    /// Same as [ProgressBar::set_progress] with a `u64`, so that counts above 4 GiB work on 32-bit targets
+    pub fn set_position(&mut self, p: u64) {
         self.progress = p;
-        if p == 0 && self.start.is_some() {
-            self.start = Some(Instant::now());
+        self.ticks += 1;
+        if p == 0 {
+            self.restart();
         }
-        self.display();
+        self.redraw();
     }
 
-    /// Set the maximum progress
+    /// Set the maximum progress, which switches an indeterminate bar to a regular one
     pub fn set_max(&mut self, m: usize) {
-        self.max = m;
+        self.set_length(m as u64)
+    }
+
+    /// Same as [ProgressBar::set_max] with a `u64`, see [ProgressBar::set_position]
+    pub fn set_length(&mut self, m: u64) {
+        self.max = Some(m);
+        self.redraw();
+    }
+
+    /// Forget the maximum progress, which switches to indeterminate mode (see [ProgressBar::new_indeterminate])
+    pub fn clear_max(&mut self) {
+        self.max = None;
+        self.display();
+    }
+
+    /// Set what is displayed in indeterminate mode (default: a `-\|/` spinner)
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::{pb::ProgressBar, spinner::{Indeterminate, Spinner}};
+    /// 
+    /// let mut progress_bar = ProgressBar::new_indeterminate();
+    /// progress_bar.set_indeterminate(Indeterminate::Spinner(Spinner::dots()));
+    /// ```
+    pub fn set_indeterminate(&mut self, indeterminate: Indeterminate) {
+        self.indeterminate = indeterminate;
         self.display();
     }
 
     /// Increment the progress by 1
     pub fn inc(&mut self) {
-        self.progress += 1;
+        self.inc_by(1)
+    }
+
+    /// Increment the progress by `n`, like the number of bytes just copied
+    pub fn inc_by(&mut self, n: u64) {
+        self.progress = self.progress.saturating_add(n);
+        self.ticks += 1;
+        self.redraw();
+    }
+
+    /// Set how the progress, the maximum and the rate are displayed (default: [Units::Items]).
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::{pb::ProgressBar, Units};
+    /// 
+    /// let mut progress_bar = ProgressBar::with_writer(0, std::io::sink());
+    /// progress_bar.set_units(Units::BinaryBytes);
+    /// progress_bar.set_length(5 << 30);
+    /// progress_bar.set_width(10);
+    /// progress_bar.inc_by(3 << 29);
+    /// assert_eq!(progress_bar.render_plain(), " [==>       ] 1.5 GiB/5.0 GiB");
+    /// ```
+    pub fn set_units(&mut self, units: Units) {
+        self.units = units;
+        self.display();
+    }
+
+    /// Set the state of a bar derived from other bars, without display update
+    pub(crate) fn aggregate(&mut self, progress: u64, max: u64) {
+        self.progress = progress;
+        self.max = Some(max);
+    }
+
+    /// Advance spinners without making progress
+    pub fn tick(&mut self) {
+        self.ticks += 1;
+        self.redraw();
+    }
+
+    /// Advance spinners and redraw the bar, unless it is finished.
+    /// Returns false if the bar is finished.
+    pub(crate) fn steady_tick(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+        self.spin();
+        self.display();
+        true
+    }
+
+    /// Mark the bar as finished, without display update
+    pub(crate) fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Advance spinners without display update
+    pub(crate) fn spin(&mut self) {
+        self.ticks += 1;
+    }
+
+    /// Limit how often the bar is redrawn when progress is made (default: no limit).  
+    /// Updates in between are only recorded, which makes [ProgressBar::inc] cheap in hot loops.
+    /// The bar is always redrawn when complete, and before being finalized.
+    /// The last update before a pause is displayed by the next redraw; use a [Ticker](crate::ticker::Ticker) so that it isn't held back,
+    /// especially for indeterminate bars, which are never complete.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::pb::ProgressBar;
+    /// 
+    /// let mut progress_bar = ProgressBar::new(1_000_000);
+    /// progress_bar.set_max_redraw_rate(Some(15));
+    /// for _ in 0..1_000_000 {
+    ///     progress_bar.inc();
+    /// }
+    /// progress_bar.finalize();
+    /// ```
+    pub fn set_max_redraw_rate(&mut self, per_second: Option<u32>) {
+        self.redraw_interval = per_second.filter(|&per_second| per_second > 0).map(|per_second| Duration::from_secs(1) / per_second);
+    }
+
+    /// Display the bar, unless it was redrawn less than the redraw interval ago
+    fn redraw(&mut self) {
+        self.finished = false;
+        self.abandoned = false;
+        let now = Instant::now();
+        self.estimator.record(self.progress, now);
+        self.throughput.record(self.progress, now);
+        if let Some(interval) = self.redraw_interval {
+            let complete = self.max.is_some_and(|max| self.progress >= max);
+            if now < self.next_redraw && !complete {
+                return;
+            }
+            self.next_redraw = now + interval;
+        }
         self.display();
     }
 
     /// **Resets progress** and enables ETA
     pub fn enable_eta(&mut self) {
         self.progress = 0;
-        self.start = Some(Instant::now());
+        self.show_eta = true;
+        self.restart();
     }
 
     /// Disables ETA
     pub fn disable_eta(&mut self) {
-        self.start = None;
+        self.show_eta = false;
+    }
+
+    /// Set how the rate of progress is estimated to compute the ETA (default: [Linear]).  
+    /// See the [estimator](crate::estimator) module for the available estimators.
+    pub fn set_estimator<E: Estimator + 'static>(&mut self, estimator: E) {
+        self.estimator = Box::new(estimator);
+        self.estimator.reset(Instant::now());
+        self.estimator.record(self.progress, Instant::now());
+    }
+
+    /// Display the rate of progress, smoothed over the last few seconds.  
+    /// When the bar is finalized, the average rate over the whole run is displayed instead.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::pb::ProgressBar;
+    /// 
+    /// let mut progress_bar = ProgressBar::new(100);
+    /// progress_bar.enable_rate();
+    /// for _ in 0..100 {
+    ///     progress_bar.inc();
+    /// }
+    /// // prints something like `[==========] 100/100 (5.2k/s)`
+    /// progress_bar.finalize();
+    /// ```
+    pub fn enable_rate(&mut self) {
+        self.show_rate = true;
+        self.display();
+    }
+
+    /// Hide the rate of progress
+    pub fn disable_rate(&mut self) {
+        self.show_rate = false;
+        self.display();
+    }
+
+    /// Display the progress in percent after the counter
+    pub fn enable_percent(&mut self) {
+        self.show_percent = true;
+        self.display();
+    }
+
+    /// Hide the progress in percent
+    pub fn disable_percent(&mut self) {
+        self.show_percent = false;
+        self.display();
+    }
+
+    /// Display the time elapsed since progress started.  
+    /// The total elapsed time is always displayed once the bar is finalized.
+    pub fn enable_elapsed(&mut self) {
+        self.show_elapsed = true;
+        self.display();
+    }
+
+    /// Hide the elapsed time while the bar is running
+    pub fn disable_elapsed(&mut self) {
+        self.show_elapsed = false;
+        self.display();
+    }
+
+    /// Get the time elapsed since progress started, or was last reset to 0
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Reset time measurements, as progress restarts from 0
+    fn restart(&mut self) {
+        let now = Instant::now();
+        self.started = now;
+        self.estimator.reset(now);
+        self.throughput.reset(now);
     }
 
     /// Set the global action displayed before the progress bar.
     pub fn set_action(&mut self, a: &str, c: Color, s: Style) {
-        self.action = ProgressBar::set_good_size(a);
+        self.action = Some(a.to_string());
         self.action_color = c;
         self.action_style = s;
         self.display();
     }
 
+    /// Set a message displayed at the end of the line, like the name of the file being processed.  
+    /// It can be changed on every update, and is truncated so that the line never wraps.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::pb::ProgressBar;
+    /// 
+    /// let files = ["a.txt", "b.txt", "c.txt"];
+    /// let mut progress_bar = ProgressBar::new(files.len());
+    /// for file in files {
+    ///     progress_bar.set_message(file);
+    ///     // process the file
+    ///     progress_bar.inc();
+    /// }
+    /// progress_bar.finalize();
+    /// ```
+    pub fn set_message(&mut self, message: &str) {
+        self.message = Some(message.replace(['\n', '\r', '\t'], " "));
+        self.redraw();
+    }
+
+    /// Remove the message set with [ProgressBar::set_message]
+    pub fn clear_message(&mut self) {
+        self.message = None;
+        self.display();
+    }
+
     /// Log something, without display update
     pub fn print_final_info(&mut self, info_name: &str, text: &str, info_color: Color, info_style: Style) {
+        self.finished = true;
         let info_name = ProgressBar::set_good_size(info_name);
-        println!("{}{}{}\x1B[0m {}\x1B[K", info_style, info_color, info_name, text);
+        let mut output = self.output();
+        #[allow(unused_must_use)]
+        { output.print_info(&info_name, text, info_color, info_style); }
+        output.last_report = None;
+        output.drawn = false;
+        drop(output);
         self.progress = 0;
+        self.restart();
     }
 
     /// Log something
     pub fn print_info(&mut self, info_name: &str, text: &str, info_color: Color, info_style: Style) {
         let info_name = ProgressBar::set_good_size(info_name);
-        println!("{}{}{}\x1B[0m {}\x1B[K", info_style, info_color, info_name, text);
+        #[allow(unused_must_use)]
+        { self.output().print_info(&info_name, text, info_color, info_style); }
         self.display();
     }
 
     /// Display the bar
     pub fn display(&self) {
-        print!("{}{}{}\x1B[0m\x1B[K", self.action_style, self.action_color, self.action);
-
-        print!(" [");
-        for i in 0..self.width {
-            if i*self.max/self.width < self.progress {
-                if (i+1)*self.max/self.width >= self.progress {
-                    print!(">");
-                } else {
-                    print!("=");
+        let mut output = self.output();
+        #[allow(unused_must_use)]
+        match output.terminal {
+            true => { self.draw(&mut output); },
+            false => if output.report_due(output.last_report, self.progress, self.max) { self.report(&mut output); },
+        }
+    }
+
+    /// Clear the line of the bar, so that something else can be printed in its place
+    #[cfg(feature = "logger")]
+    pub(crate) fn clear(&self) {
+        let mut output = self.output();
+        if output.terminal {
+            #[allow(unused_must_use)]
+            {
+                write!(output.writer, "\r\x1B[K\r");
+                output.writer.flush();
+            }
+        }
+    }
+
+    /// Render the current state of the bar as a single line, including ANSI styling codes.  
+    /// This is exactly what [ProgressBar::display] prints, without the cursor movements.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::pb::ProgressBar;
+    /// 
+    /// let mut progress_bar = ProgressBar::with_writer(4, std::io::sink());
+    /// progress_bar.set_width(4);
+    /// assert_eq!(progress_bar.render(), "\x1B[0m\x1B[30m\x1B[0m [    ] 0/4");
+    /// ```
+    pub fn render(&self) -> String {
+        let columns = self.output().columns();
+        self.render_frame(true, columns)
+    }
+
+    /// Same as [ProgressBar::render] but without any ANSI code, which makes it convenient for snapshot tests.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::{pb::ProgressBar, Color, Style};
+    /// 
+    /// let mut progress_bar = ProgressBar::with_writer(4, std::io::sink());
+    /// progress_bar.set_width(8);
+    /// progress_bar.set_action("Loading", Color::Blue, Style::Bold);
+    /// progress_bar.inc();
+    /// assert_eq!(progress_bar.render_plain(), "     Loading [=>      ] 1/4");
+    /// ```
+    pub fn render_plain(&self) -> String {
+        let columns = self.output().columns();
+        self.render_frame(false, columns)
+    }
+
+    /// The action, padded to 12 caracters as displayed by the default layout
+    fn padded_action(&self) -> String {
+        self.action.as_deref().map(ProgressBar::set_good_size).unwrap_or_default()
+    }
+
+    fn styled(&self, text: &str, ansi: bool) -> String {
+        match ansi {
+            true => format!("{}{}{}\x1B[0m", self.action_style, self.action_color, text),
+            false => text.to_string(),
+        }
+    }
+
+    /// Width of the bar so that the default layout fits in `columns`.  
+    /// Enough room is kept for the longest counter and ETA, so that the bar doesn't jitter when they change.
+    fn bar_width(&self, columns: Option<usize>) -> usize {
+        const ETA_RESERVE: usize = " (ETA 110 minutes)".len();
+        const RATE_RESERVE: usize = " (999.9k/min)".len();
+        const BYTE_RATE_RESERVE: usize = " (1023.9 KiB/s)".len();
+        const PERCENT_RESERVE: usize = " 100%".len();
+        const ELAPSED_RESERVE: usize = " (elapsed 99h59m)".len();
+
+        let Some(columns) = columns else {
+            return self.width.unwrap_or(50);
+        };
+        let brackets = term::width(&self.bar_glyphs.open) + term::width(&self.bar_glyphs.close);
+        let counter = match (self.max, self.units) {
+            (Some(max), Units::Items) => format!("  {max}/{max}"),
+            (Some(_), _) => format!("  {0}/{0}", "1023.9 KiB"),
+            (None, _) => format!("  {}", self.indeterminate_status()),
+        };
+        let mut reserved = term::width(&self.padded_action()) + brackets + counter.len();
+        // The ETA is replaced by the total elapsed time once finished
+        if self.finished {
+            reserved += self.done().len();
+        } else if self.show_eta {
+            reserved += ETA_RESERVE;
+        }
+        if self.show_percent && self.max.is_some() {
+            reserved += PERCENT_RESERVE;
+        }
+        if self.show_elapsed && self.max.is_some() && !self.finished {
+            reserved += ELAPSED_RESERVE;
+        }
+        if self.show_rate && self.max.is_some() {
+            reserved += match self.units {
+                Units::Items => RATE_RESERVE,
+                _ => BYTE_RATE_RESERVE,
+            };
+        }
+        // A steady share of the line is kept for the message, which is truncated if longer
+        if let Some(message) = &self.message {
+            reserved += (term::width(message) + 1).min(columns / 3);
+        }
+        let available = columns.saturating_sub(reserved + 1);
+        self.width.map_or(available, |width| width.min(available))
+    }
+
+    /// The bar itself, with `width` columns between brackets
+    fn bar(&self, width: usize) -> String {
+        let smooth = match self.bar_mode {
+            BarMode::Classic => false,
+            BarMode::Smooth => true,
+            BarMode::Auto => term::utf8_locale(),
+        };
+        let glyphs = &self.bar_glyphs;
+        let mut bar = glyphs.open.clone();
+        let mut used = match (self.max, smooth) {
+            (None, _) => self.bounce_fill(&mut bar, width),
+            (Some(max), true) => self.smooth_fill(&mut bar, max, width),
+            (Some(max), false) => {
+                let cell = |i: usize| (i as u128 * max as u128 / width as u128) as u64;
+                let filled = (0..width).filter(|&i| cell(i) < self.progress).count();
+                let head = filled > 0 && cell(filled) >= self.progress;
+                let head_width = if head { term::width(&glyphs.head) } else { 0 };
+                let mut used = term::repeat(&mut bar, &glyphs.fill, filled.saturating_sub(head_width));
+                if head && used + head_width <= width {
+                    bar.push_str(&glyphs.head);
+                    used += head_width;
                 }
-            } else {
-                print!(" ");
+                used
+            }
+        };
+        used += term::repeat(&mut bar, &glyphs.empty, width - used);
+        bar.extend(std::iter::repeat_n(' ', width - used));
+        bar.push_str(&glyphs.close);
+        bar
+    }
+
+    /// Fill the bar with eighths of cells, returning the number of columns used
+    fn smooth_fill(&self, bar: &mut String, max: u64, width: usize) -> usize {
+        const PARTIAL_BLOCKS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+        let eighths = match max {
+            0 => 0,
+            max => (self.progress.min(max) as u128 * width as u128 * 8 / max as u128) as usize,
+        };
+        let (full, partial) = (eighths / 8, eighths % 8);
+        bar.extend(std::iter::repeat_n('█', full));
+        if partial > 0 {
+            bar.push(PARTIAL_BLOCKS[partial - 1]);
+        }
+        full + (partial > 0) as usize
+    }
+
+    /// Fill the bar with a block going back and forth, returning the number of columns used
+    fn bounce_fill(&self, bar: &mut String, width: usize) -> usize {
+        const BLOCK: &str = "<=>";
+
+        let Some(room) = width.checked_sub(BLOCK.len()) else {
+            return 0;
+        };
+        let position = match room {
+            0 => 0,
+            room => match self.ticks % (2 * room) {
+                tick if tick <= room => tick,
+                tick => 2 * room - tick,
+            },
+        };
+        bar.extend(std::iter::repeat_n(' ', position));
+        bar.push_str(BLOCK);
+        position + BLOCK.len()
+    }
+
+    /// The current frame of the spinner
+    fn spinner(&self) -> String {
+        match &self.indeterminate {
+            Indeterminate::Spinner(spinner) => spinner.frame(self.ticks).to_string(),
+            Indeterminate::Bounce => Spinner::default().frame(self.ticks).to_string(),
+        }
+    }
+
+    /// Progress, elapsed time and rate, displayed in indeterminate mode
+    fn indeterminate_status(&self) -> String {
+        let progress = format::count(self.progress, self.units);
+        format!("{} ({}, {})", progress, format::duration(self.started.elapsed()), format::rate_in(self.rate(), self.units))
+    }
+
+    /// Smoothed rate of progress, or the average rate once finished
+    fn rate(&self) -> f64 {
+        let now = Instant::now();
+        let average = self.progress as f64 / now.saturating_duration_since(self.started).as_secs_f64().max(0.001);
+        match self.finished {
+            true => average,
+            false => self.throughput.rate(now).unwrap_or(average),
+        }
+    }
+
+    pub(crate) fn render_frame(&self, ansi: bool, columns: Option<usize>) -> String {
+        let frame = match &self.template {
+            Some(template) => self.render_template(template, ansi, columns),
+            None => self.render_default(ansi, columns),
+        };
+        match columns {
+            // The last column is left empty so that the line never wraps
+            Some(columns) => term::truncate(&frame, columns.saturating_sub(1)),
+            None => frame,
+        }
+    }
+
+    fn render_default(&self, ansi: bool, columns: Option<usize>) -> String {
+        let mut frame = self.styled(&self.padded_action(), ansi);
+        frame.push(' ');
+        let Some(max) = self.max else {
+            match self.indeterminate {
+                Indeterminate::Spinner(_) => frame.push_str(&self.spinner()),
+                Indeterminate::Bounce => frame.push_str(&self.bar(self.bar_width(columns))),
+            }
+            frame.push_str(&format!(" {}", self.indeterminate_status()));
+            if self.abandoned {
+                frame.push_str(&self.done());
             }
+            self.push_message(&mut frame);
+            return frame;
+        };
+        frame.push_str(&self.bar(self.bar_width(columns)));
+        frame.push_str(&format!(" {}/{}", format::count(self.progress, self.units), format::count(max, self.units)));
+        if self.show_percent {
+            frame.push_str(&format!(" {}%", self.percent(max)));
         }
-        print!("] {}/{}", self.progress, self.max);
-        if let Some(start) = self.start {
-            if self.max != 0 && self.progress != 0 && self.progress != self.max {
-                let elapsed = start.elapsed();
-                let progress_rate = self.progress as f64 / self.max as f64;
-                let inv_progress_rate = 1. - progress_rate;
-                let total_time = elapsed.as_millis() as f64 / progress_rate;
-                let remaining_time = total_time * inv_progress_rate;
-                let remaining_ms = remaining_time.ceil() as usize;
-
-                const SECS_110: usize = 110 * 1000;
-                const MINS_110: usize = 110 * 60 * 1000;
-                const HOURS_46: usize = 46 * 60 * 60 * 1000;
-
-                #[allow(overlapping_range_endpoints)]
-                #[allow(clippy::match_overlapping_arm)]
-                let eta = match remaining_ms {
-                    0..=3_000 => format!("{}ms", remaining_time.ceil() as usize),
-                    3_001..=SECS_110 => format!("{}s", (remaining_time / 1000.).ceil() as usize),
-                    SECS_110..=MINS_110 => format!("{} minutes", (remaining_time / (1000. * 60.)).ceil() as usize),
-                    MINS_110..=HOURS_46 => format!("{} hours", (remaining_time / (1000. * 60. * 60.)).ceil() as usize),
-                    _ => format!("{} days", (remaining_time / (1000. * 60. * 60. * 24.)).ceil() as usize),
-                };
-
-                print!(" (ETA {eta})");
+        if self.show_rate {
+            frame.push_str(&format!(" ({})", format::rate_in(self.rate(), self.units)));
+        }
+        if self.finished {
+            frame.push_str(&self.done());
+        } else {
+            if self.show_elapsed {
+                frame.push_str(&format!(" (elapsed {})", format::duration(self.elapsed())));
+            }
+            if let Some(eta) = self.eta() {
+                frame.push_str(&format!(" (ETA {eta})"));
             }
         }
-        print!("\n\x1B[1A");
+        self.push_message(&mut frame);
+        frame
+    }
 
-        #[allow(unused_must_use)]
-        { io::stdout().flush(); }
+    fn push_message(&self, frame: &mut String) {
+        if let Some(message) = &self.message {
+            frame.push(' ');
+            frame.push_str(message);
+        }
+    }
+
+    fn field(&self, key: Key) -> String {
+        match key {
+            Key::Action => self.action.clone().unwrap_or_default(),
+            Key::Bar => unreachable!("bars are rendered last"),
+            Key::Pos => format::count(self.progress, self.units),
+            Key::Len => self.max.map_or(String::from("?"), |max| format::count(max, self.units)),
+            Key::Percent => self.max.map_or(String::from("?"), |max| self.percent(max).to_string()),
+            Key::Elapsed => format::duration(self.elapsed()),
+            Key::Msg => self.message.clone().unwrap_or_default(),
+            Key::Spinner => self.spinner(),
+            Key::Eta => self.eta().unwrap_or_default(),
+            Key::Rate => format::rate_in(self.rate(), self.units),
+        }
+    }
+
+    /// Render all fields, then share the remaining columns between bars
+    fn render_template(&self, template: &Template, ansi: bool, columns: Option<usize>) -> String {
+        let mut used = 0;
+        let mut bars = 0;
+        let pieces: Vec<Option<String>> = template.pieces.iter().map(|piece| match piece {
+            Piece::Literal(text) => {
+                used += term::width(text);
+                Some(text.clone())
+            },
+            Piece::Field { key: Key::Bar, .. } => {
+                bars += 1;
+                used += term::width(&self.bar_glyphs.open) + term::width(&self.bar_glyphs.close);
+                None
+            },
+            Piece::Field { key, align, width } => {
+                let text = template::pad(&self.field(*key), *align, *width);
+                used += term::width(&text);
+                match key {
+                    Key::Action => Some(self.styled(&text, ansi)),
+                    _ => Some(text),
+                }
+            },
+        }).collect();
+
+        let available = columns.map(|columns| columns.saturating_sub(used + 1) / bars.max(1));
+        let mut frame = String::new();
+        for (piece, rendered) in template.pieces.iter().zip(pieces) {
+            match (piece, rendered) {
+                (_, Some(text)) => frame.push_str(&text),
+                (Piece::Field { width, .. }, None) => {
+                    let width = width.or(self.width);
+                    let width = match available {
+                        Some(available) => width.map_or(available, |width| width.min(available)),
+                        None => width.unwrap_or(50),
+                    };
+                    frame.push_str(&self.bar(width));
+                },
+                (Piece::Literal(_), None) => unreachable!(),
+            }
+        }
+        frame
+    }
+
+    /// Total elapsed time, displayed once finished
+    fn done(&self) -> String {
+        match self.abandoned {
+            true => format!(" (abandoned after {})", format::duration(self.elapsed())),
+            false => format!(" (done in {})", format::duration(self.elapsed())),
+        }
+    }
+
+    fn percent(&self, max: u64) -> u64 {
+        match max {
+            0 => 0,
+            max => (self.progress as u128 * 100 / max as u128) as u64,
+        }
+    }
+
+    fn eta(&self) -> Option<String> {
+        if !self.show_eta {
+            return None;
+        }
+        let max = self.max?;
+        if max == 0 || self.progress == 0 || self.progress >= max {
+            return None;
+        }
+
+        let rate = self.estimator.rate(Instant::now()).filter(|&rate| rate > 0.)?;
+        let remaining_time = (max - self.progress) as f64 / rate * 1000.;
+        let remaining_ms = remaining_time.ceil() as usize;
+
+        const SECS_110: usize = 110 * 1000;
+        const MINS_110: usize = 110 * 60 * 1000;
+        const HOURS_46: usize = 46 * 60 * 60 * 1000;
+
+        #[allow(overlapping_range_endpoints)]
+        #[allow(clippy::match_overlapping_arm)]
+        let eta = match remaining_ms {
+            0..=3_000 => format!("{}ms", remaining_time.ceil() as usize),
+            3_001..=SECS_110 => format!("{}s", (remaining_time / 1000.).ceil() as usize),
+            SECS_110..=MINS_110 => format!("{} minutes", (remaining_time / (1000. * 60.)).ceil() as usize),
+            MINS_110..=HOURS_46 => format!("{} hours", (remaining_time / (1000. * 60. * 60.)).ceil() as usize),
+            _ => format!("{} days", (remaining_time / (1000. * 60. * 60. * 24.)).ceil() as usize),
+        };
+        Some(eta)
+    }
+
+    fn draw(&self, output: &mut Output) -> io::Result<()> {
+        output.drawn = true;
+        let frame = self.render_frame(true, output.columns());
+        write!(output.writer, "{}\x1B[K\n\x1B[1A", frame)?;
+        output.writer.flush()
+    }
+
+    /// Print a plain status line
+    fn report(&self, output: &mut Output) -> io::Result<()> {
+        output.last_report = Some((self.progress, Instant::now()));
+        output.drawn = true;
+        let line = self.render_frame(false, output.columns());
+        writeln!(output.writer, "{}", line)?;
+        output.writer.flush()
     }
     
     /// Mark the end of the progress bar - updates will make a 'new' bar
     pub fn finalize(&mut self) {
+        self.end(false);
+    }
+
+    /// Same as [ProgressBar::finalize] but the bar is marked as abandoned, for work that failed or was interrupted.  
+    /// The final line shows how long the bar ran instead of the completion time.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use progress_bar::pb::ProgressBar;
+    /// 
+    /// let mut progress_bar = ProgressBar::with_writer(10, std::io::sink());
+    /// progress_bar.inc();
+    /// progress_bar.abandon();
+    /// ```
+    pub fn abandon(&mut self) {
+        self.end(true);
+    }
+
+    fn end(&mut self, abandoned: bool) {
+        // The final frame shows the total elapsed time and the average rate
+        self.finished = true;
+        self.abandoned = abandoned;
+        let mut output = self.output();
+        #[allow(unused_must_use)]
+        match output.terminal {
+            true => {
+                self.draw(&mut output);
+                writeln!(output.writer);
+                output.writer.flush();
+            },
+            false => { self.report(&mut output); },
+        }
+        output.last_report = None;
+        output.drawn = false;
+        drop(output);
         self.progress = 0;
-        println!();
+        self.restart();
+    }
+}
+
+/// A bar dropped while displayed is finalized, so that the terminal is not left with a half-drawn line.  
+/// If the thread is panicking, the bar is abandoned instead.
+impl Drop for ProgressBar {
+    fn drop(&mut self) {
+        if self.finished || !self.output().drawn {
+            return;
+        }
+        match std::thread::panicking() {
+            // A second panic while unwinding would abort the process
+            true => {
+                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.abandon()));
+            },
+            false => self.finalize(),
+        }
     }
 }
@@ -0,0 +1,154 @@
+//! A progress bar that can be updated from several threads without locking.
+
+use crate::pb::ProgressBar;
+use crate::ticker::{self, Ticker};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+/// How often the bar is redrawn by default
+const RENDER_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A cheap, cloneable handle on a progress bar.
+///
+/// Progress is counted with atomics, so worker threads never wait on each other.
+/// A background thread renders the bar from the counter at a steady interval.
+/// The bar is finalized when [SharedProgressBar::finalize] is called, or when the last handle is dropped.
+///
+/// # Example
+///
+/// ```
+/// use progress_bar::{pb::ProgressBar, shared::SharedProgressBar};
+/// use std::thread;
+///
+/// let progress_bar = SharedProgressBar::new(ProgressBar::new(400));
+/// thread::scope(|scope| {
+///     for _ in 0..4 {
+///         let progress_bar = progress_bar.clone();
+///         scope.spawn(move || {
+///             for _ in 0..100 {
+///                 // do some work
+///                 progress_bar.inc();
+///             }
+///         });
+///     }
+/// });
+/// progress_bar.finalize();
+/// ```
+#[derive(Clone)]
+pub struct SharedProgressBar {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    position: AtomicU64,
+    progress_bar: Mutex<ProgressBar>,
+    /// None once finalized
+    ticker: Mutex<Option<Ticker>>,
+}
+
+impl Shared {
+    fn progress_bar(&self) -> MutexGuard<'_, ProgressBar> {
+        self.progress_bar.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Stop rendering, then draw and finalize the bar with the final count
+    fn finalize(&self) {
+        // The ticker must be stopped before locking the bar, as it locks it too
+        let ticker = self.ticker.lock().unwrap_or_else(PoisonError::into_inner).take();
+        if ticker.is_none() {
+            return;
+        }
+        drop(ticker);
+        let mut progress_bar = self.progress_bar();
+        progress_bar.set_position(self.position.swap(0, Ordering::Relaxed));
+        progress_bar.finalize();
+    }
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        self.finalize();
+    }
+}
+
+impl SharedProgressBar {
+    /// Share `progress_bar`, which is redrawn every 50ms
+    pub fn new(progress_bar: ProgressBar) -> Self {
+        SharedProgressBar::with_render_interval(progress_bar, RENDER_INTERVAL)
+    }
+
+    /// Share `progress_bar`, which is redrawn every `interval`
+    pub fn with_render_interval(progress_bar: ProgressBar, interval: Duration) -> Self {
+        let shared = Arc::new(Shared {
+            position: AtomicU64::new(progress_bar.position()),
+            progress_bar: Mutex::new(progress_bar),
+            ticker: Mutex::new(None),
+        });
+        let weak = Arc::downgrade(&shared);
+        let ticker = Ticker::spawn(interval, move || {
+            let Some(shared) = weak.upgrade() else {
+                return false;
+            };
+            let Some(mut progress_bar) = ticker::try_lock(&shared.progress_bar) else {
+                return true;
+            };
+            let position = shared.position.load(Ordering::Relaxed);
+            match progress_bar.position() == position {
+                true => progress_bar.steady_tick(),
+                false => {
+                    progress_bar.set_position(position);
+                    true
+                }
+            }
+        });
+        *shared.ticker.lock().unwrap_or_else(PoisonError::into_inner) = Some(ticker);
+        SharedProgressBar { shared }
+    }
+
+    /// Increment the progress by 1
+    pub fn inc(&self) {
+        self.inc_by(1);
+    }
+
+    /// Increment the progress by `n`
+    pub fn inc_by(&self, n: u64) {
+        self.shared.position.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Set the progress
+    pub fn set_progress(&self, progress: usize) {
+        self.set_position(progress as u64);
+    }
+
+    /// Same as [SharedProgressBar::set_progress] with a `u64`
+    pub fn set_position(&self, position: u64) {
+        self.shared.position.store(position, Ordering::Relaxed);
+    }
+
+    /// Get the current progress
+    pub fn position(&self) -> u64 {
+        self.shared.position.load(Ordering::Relaxed)
+    }
+
+    /// Lock the bar to change its settings, like its action or message.
+    /// Progress should be changed through the handle instead, as it overrides the progress of the bar.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use progress_bar::{pb::ProgressBar, shared::SharedProgressBar, Color, Style};
+    ///
+    /// let progress_bar = SharedProgressBar::new(ProgressBar::new(10));
+    /// progress_bar.update(|progress_bar| progress_bar.set_action("Loading", Color::Blue, Style::Bold));
+    /// ```
+    pub fn update<R>(&self, f: impl FnOnce(&mut ProgressBar) -> R) -> R {
+        f(&mut self.shared.progress_bar())
+    }
+
+    /// Stop rendering and finalize the bar with the final progress.
+    /// Updates made afterwards are not displayed.
+    pub fn finalize(&self) {
+        self.shared.finalize();
+    }
+}
@@ -0,0 +1,61 @@
+//! Animations for progress bars whose total is unknown.
+
+/// A set of frames displayed one after the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spinner {
+    frames: Vec<String>,
+}
+
+impl Spinner {
+    /// Creates a spinner from its frames.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `frames` is empty.
+    pub fn new(frames: &[&str]) -> Self {
+        assert!(!frames.is_empty(), "a spinner needs at least one frame");
+        Spinner {
+            frames: frames.iter().map(|frame| frame.to_string()).collect(),
+        }
+    }
+
+    /// `-\|/`
+    pub fn line() -> Self {
+        Spinner::new(&["-", "\\", "|", "/"])
+    }
+
+    /// `⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏`
+    pub fn dots() -> Self {
+        Spinner::new(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"])
+    }
+
+    /// `◐◓◑◒`
+    pub fn circle() -> Self {
+        Spinner::new(&["◐", "◓", "◑", "◒"])
+    }
+
+    pub(crate) fn frame(&self, tick: usize) -> &str {
+        &self.frames[tick % self.frames.len()]
+    }
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Spinner::line()
+    }
+}
+
+/// What is displayed instead of the bar when the total is unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Indeterminate {
+    /// A spinner in place of the bar
+    Spinner(Spinner),
+    /// A block bouncing from one end of the bar to the other: `[   <=>    ]`
+    Bounce,
+}
+
+impl Default for Indeterminate {
+    fn default() -> Self {
+        Indeterminate::Spinner(Spinner::default())
+    }
+}
@@ -71,4 +71,62 @@ impl fmt::Display for Style {
             Style::StrikeThrough => write!(f, "\x1B[9m"),
         }
     }
-}
\ No newline at end of file
+}
+
+/// How the bar is drawn.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum BarMode {
+    /// `[=====>    ]`, moving one whole cell at a time
+    #[default]
+    Classic,
+    /// `[█████▍    ]`, using Unicode partial blocks to show eighths of a cell
+    Smooth,
+    /// [BarMode::Smooth] if the locale supports UTF-8, [BarMode::Classic] otherwise
+    Auto,
+}
+
+/// How the progress, the maximum and the rate are displayed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Units {
+    /// Plain numbers, like `1536/4096 (12.5/s)`
+    #[default]
+    Items,
+    /// Bytes with binary prefixes, like `1.5 KiB/4.0 KiB (12.5 KiB/s)`
+    BinaryBytes,
+    /// Bytes with decimal prefixes, like `1.5 kB/4.1 kB (12.8 kB/s)`
+    DecimalBytes,
+}
+
+/// The strings a bar is drawn with.  
+/// They can be longer than one character and take several columns, like emojis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BarGlyphs {
+    /// Repeated in the completed part of the bar
+    pub fill: String,
+    /// Drawn at the end of the completed part of the bar
+    pub head: String,
+    /// Repeated in the remaining part of the bar
+    pub empty: String,
+    /// Drawn before the bar
+    pub open: String,
+    /// Drawn after the bar
+    pub close: String,
+}
+
+impl BarGlyphs {
+    pub fn new(fill: &str, head: &str, empty: &str, open: &str, close: &str) -> Self {
+        BarGlyphs {
+            fill: fill.to_string(),
+            head: head.to_string(),
+            empty: empty.to_string(),
+            open: open.to_string(),
+            close: close.to_string(),
+        }
+    }
+}
+
+impl Default for BarGlyphs {
+    fn default() -> Self {
+        BarGlyphs::new("=", ">", " ", "[", "]")
+    }
+}
@@ -0,0 +1,189 @@
+//! Templates describing the layout of the progress line.
+//! 
+//! A template is a string in which placeholders between braces are replaced by the state of the bar.
+//! Each placeholder may have a format spec after a colon, with an optional alignment (`<`, `^` or `>`) and a width.
+//! Use `{{` and `}}` to write literal braces.
+//! 
+//! | Placeholder | Content |
+//! |-------------|---------|
+//! | `{action}`  | The action set with [`ProgressBar::set_action`](crate::pb::ProgressBar::set_action) |
+//! | `{bar}`     | The bar and its brackets, filling the remaining width unless a width is given. An alignment is accepted but has no effect, as the bar always fills its width |
+//! | `{pos}`     | The current progress |
+//! | `{len}`     | The maximum progress |
+//! | `{percent}` | The progress in percent |
+//! | `{elapsed}` | The time elapsed since progress started |
+//! | `{eta}`     | The estimated remaining time, when ETA is enabled |
+//! | `{rate}`    | The rate of progress, smoothed over the last few seconds (see [`ProgressBar::enable_rate`](crate::pb::ProgressBar::enable_rate)) |
+//! | `{msg}`     | The message set with [`ProgressBar::set_message`](crate::pb::ProgressBar::set_message), give it a width to keep the bar steady |
+//! | `{spinner}` | A spinner, animated on each update (see [`Indeterminate`](crate::spinner::Indeterminate)) |
+//! 
+//! # Example
+//! 
+//! ```
+//! use progress_bar::{pb::ProgressBar, template::Template};
+//! 
+//! let template = Template::parse("{action:>8} {bar:10} {percent:>3}% {pos}/{len}").unwrap();
+//! let mut progress_bar = ProgressBar::with_writer(4, std::io::sink());
+//! progress_bar.set_template(template);
+//! progress_bar.inc();
+//! assert_eq!(progress_bar.render_plain(), "         [==>       ]  25% 1/4");
+//! ```
+
+use crate::term;
+use std::{error::Error, fmt, str::FromStr};
+
+/// The values that can be displayed in a template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Key {
+    Action,
+    Bar,
+    Pos,
+    Len,
+    Percent,
+    Elapsed,
+    Eta,
+    Rate,
+    Msg,
+    Spinner,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Piece {
+    Literal(String),
+    Field { key: Key, align: Align, width: Option<usize> },
+}
+
+/// A parsed layout for the progress line.  
+/// See the [module documentation](self) for the syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    pub(crate) pieces: Vec<Piece>,
+}
+
+/// An error encountered while parsing a [`Template`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// A placeholder is not closed by `}`
+    UnclosedPlaceholder,
+    /// A `}` doesn't close any placeholder (use `}}` for a literal brace)
+    UnmatchedBrace,
+    /// The placeholder name is not known
+    UnknownPlaceholder(String),
+    /// The format spec after the colon is not an alignment followed by a width
+    InvalidSpec(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::UnclosedPlaceholder => write!(f, "unclosed placeholder in template"),
+            TemplateError::UnmatchedBrace => write!(f, "unmatched '}}' in template"),
+            TemplateError::UnknownPlaceholder(name) => write!(f, "unknown placeholder {{{name}}} in template"),
+            TemplateError::InvalidSpec(spec) => write!(f, "invalid format spec {spec:?} in template"),
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+impl Key {
+    fn parse(name: &str) -> Result<Key, TemplateError> {
+        match name {
+            "action" => Ok(Key::Action),
+            "bar" => Ok(Key::Bar),
+            "pos" => Ok(Key::Pos),
+            "len" => Ok(Key::Len),
+            "percent" => Ok(Key::Percent),
+            "elapsed" => Ok(Key::Elapsed),
+            "eta" => Ok(Key::Eta),
+            "rate" => Ok(Key::Rate),
+            "msg" => Ok(Key::Msg),
+            "spinner" => Ok(Key::Spinner),
+            _ => Err(TemplateError::UnknownPlaceholder(name.to_string())),
+        }
+    }
+}
+
+fn parse_field(field: &str) -> Result<Piece, TemplateError> {
+    let (name, spec) = field.split_once(':').unwrap_or((field, ""));
+    let key = Key::parse(name.trim())?;
+    let (align, width) = match spec.chars().next() {
+        Some('<') => (Align::Left, &spec[1..]),
+        Some('^') => (Align::Center, &spec[1..]),
+        Some('>') => (Align::Right, &spec[1..]),
+        _ => (Align::Left, spec),
+    };
+    let width = match width {
+        "" => None,
+        width => Some(width.parse().map_err(|_| TemplateError::InvalidSpec(spec.to_string()))?),
+    };
+    Ok(Piece::Field { key, align, width })
+}
+
+impl Template {
+    /// Parse a template.  
+    /// See the [module documentation](self) for the syntax.
+    pub fn parse(template: &str) -> Result<Template, TemplateError> {
+        let mut pieces = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => return Err(TemplateError::UnmatchedBrace),
+                '{' => {
+                    let mut field = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => field.push(c),
+                            None => return Err(TemplateError::UnclosedPlaceholder),
+                        }
+                    }
+                    if !literal.is_empty() {
+                        pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                    }
+                    pieces.push(parse_field(&field)?);
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            pieces.push(Piece::Literal(literal));
+        }
+        Ok(Template { pieces })
+    }
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(template: &str) -> Result<Template, TemplateError> {
+        Template::parse(template)
+    }
+}
+
+/// Pad `text` to `width` columns according to `align`
+pub(crate) fn pad(text: &str, align: Align, width: Option<usize>) -> String {
+    let missing = width.unwrap_or(0).saturating_sub(term::width(text));
+    let (left, right) = match align {
+        Align::Left => (0, missing),
+        Align::Center => (missing / 2, missing - missing / 2),
+        Align::Right => (missing, 0),
+    };
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
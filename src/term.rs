@@ -0,0 +1,115 @@
+//! Terminal size detection.
+
+use std::sync::OnceLock;
+use unicode_width::UnicodeWidthChar;
+
+#[cfg(unix)]
+mod unix {
+    use std::os::fd::RawFd;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Once;
+
+    /// Incremented each time the terminal is resized
+    static RESIZE_GENERATION: AtomicUsize = AtomicUsize::new(0);
+    static INSTALL_HANDLER: Once = Once::new();
+
+    extern "C" fn on_resize(_: libc::c_int) {
+        RESIZE_GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of times the terminal has been resized.  
+    /// A SIGWINCH handler is installed on first call, unless the application already set one.
+    pub(crate) fn resize_generation() -> usize {
+        INSTALL_HANDLER.call_once(|| unsafe {
+            let mut previous: libc::sigaction = std::mem::zeroed();
+            if libc::sigaction(libc::SIGWINCH, std::ptr::null(), &mut previous) == 0 && previous.sa_sigaction == libc::SIG_DFL {
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = on_resize as extern "C" fn(libc::c_int) as libc::sighandler_t;
+                action.sa_flags = libc::SA_RESTART;
+                libc::sigemptyset(&mut action.sa_mask);
+                libc::sigaction(libc::SIGWINCH, &action, std::ptr::null_mut());
+            }
+        });
+        RESIZE_GENERATION.load(Ordering::Relaxed)
+    }
+
+    /// Number of columns of the terminal behind `fd`
+    pub(crate) fn columns(fd: RawFd) -> Option<usize> {
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        match unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } {
+            0 if size.ws_col > 0 => Some(size.ws_col as usize),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(crate) use unix::*;
+
+/// Whether the locale uses UTF-8, according to the `LC_ALL`, `LC_CTYPE` and `LANG` environment variables
+pub(crate) fn utf8_locale() -> bool {
+    static UTF8_LOCALE: OnceLock<bool> = OnceLock::new();
+    *UTF8_LOCALE.get_or_init(|| {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        locale.contains("utf-8") || locale.contains("utf8")
+    })
+}
+
+/// Number of columns taken by `text`, ignoring ANSI escape codes
+pub(crate) fn width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1B' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            width += c.width().unwrap_or(0);
+        }
+    }
+    width
+}
+
+/// Push `glyph` as many times as it fits in `columns`, returning the number of columns used
+pub(crate) fn repeat(out: &mut String, glyph: &str, columns: usize) -> usize {
+    let glyph_width = width(glyph);
+    if glyph_width == 0 {
+        return 0;
+    }
+    let count = columns / glyph_width;
+    out.extend(std::iter::repeat_n(glyph, count));
+    count * glyph_width
+}
+
+/// Cut `line` so that it takes at most `columns` columns, ignoring ANSI escape codes
+pub(crate) fn truncate(line: &str, columns: usize) -> String {
+    let mut truncated = String::with_capacity(line.len());
+    let mut visible = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1B' {
+            truncated.push(c);
+            for c in chars.by_ref() {
+                truncated.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+        let c_width = c.width().unwrap_or(0);
+        if visible + c_width > columns {
+            if line.contains('\x1B') {
+                truncated.push_str("\x1B[0m");
+            }
+            break;
+        }
+        truncated.push(c);
+        visible += c_width;
+    }
+    truncated
+}
@@ -0,0 +1,92 @@
+//! Background threads redrawing progress bars at a steady interval.
+
+use crate::pb::ProgressBar;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError, TryLockError, Weak};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// A background thread redrawing a progress bar at a steady interval, so that the elapsed time, the ETA and spinners
+/// keep updating even when no progress is made.
+/// 
+/// The thread stops when the bar is finalized or dropped, or when the ticker is dropped.
+/// 
+/// # Example
+/// 
+/// ```
+/// use progress_bar::{pb::ProgressBar, ticker::Ticker};
+/// use std::sync::{Arc, Mutex};
+/// use std::{thread, time::Duration};
+/// 
+/// let progress_bar = Arc::new(Mutex::new(ProgressBar::new_indeterminate()));
+/// let ticker = Ticker::new(&progress_bar, Duration::from_millis(100));
+/// 
+/// // a slow step, during which the spinner keeps spinning
+/// thread::sleep(Duration::from_millis(500));
+/// 
+/// progress_bar.lock().unwrap().finalize();
+/// ```
+pub struct Ticker {
+    stopped: Arc<(Mutex<bool>, Condvar)>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Lock `mutex` for a tick, or return None if it is busy.  
+/// Ticks never wait for a lock, so that a ticker can be dropped by a thread holding it without deadlocking on the join.
+pub(crate) fn try_lock<T>(mutex: &Mutex<T>) -> Option<MutexGuard<'_, T>> {
+    match mutex.try_lock() {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
+impl Ticker {
+    /// Spawn a thread calling `tick` every `interval`, until it returns false or the ticker is dropped
+    pub(crate) fn spawn(interval: Duration, mut tick: impl FnMut() -> bool + Send + 'static) -> Ticker {
+        let stopped = Arc::new((Mutex::new(false), Condvar::new()));
+        let thread_stopped = Arc::clone(&stopped);
+        let thread = thread::spawn(move || {
+            let (lock, condvar) = &*thread_stopped;
+            loop {
+                let guard = lock.lock().unwrap_or_else(PoisonError::into_inner);
+                let (guard, _) = condvar
+                    .wait_timeout_while(guard, interval, |stopped| !*stopped)
+                    .unwrap_or_else(PoisonError::into_inner);
+                if *guard {
+                    break;
+                }
+                drop(guard);
+                if !tick() {
+                    break;
+                }
+            }
+        });
+        Ticker { stopped, thread: Some(thread) }
+    }
+
+    /// Redraw `progress_bar` every `interval` in a background thread
+    pub fn new(progress_bar: &Arc<Mutex<ProgressBar>>, interval: Duration) -> Ticker {
+        let progress_bar: Weak<Mutex<ProgressBar>> = Arc::downgrade(progress_bar);
+        Ticker::spawn(interval, move || match progress_bar.upgrade() {
+            Some(progress_bar) => try_lock(&progress_bar).is_none_or(|mut progress_bar| progress_bar.steady_tick()),
+            None => false,
+        })
+    }
+
+    /// Stop the thread and wait for it to exit
+    pub fn stop(self) {}
+}
+
+impl Drop for Ticker {
+    fn drop(&mut self) {
+        let (lock, condvar) = &*self.stopped;
+        *lock.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        condvar.notify_all();
+        if let Some(thread) = self.thread.take() {
+            // The last reference to the bar may be dropped by the thread itself
+            if thread.thread().id() != thread::current().id() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use progress_bar::asynchronous::*;
+use progress_bar::pb::ProgressBar;
+use progress_bar::{has_progress_bar, init_progress_bar_with_writer, ProgressBarError, CURRENT_PROGRESS_BAR};
+use std::future::poll_fn;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use common::SharedBuffer;
+
+/// Yields the numbers in `0..len`, one per poll
+struct Counter {
+    next: usize,
+    len: usize,
+}
+
+impl futures_core::Stream for Counter {
+    type Item = usize;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<usize>> {
+        if self.next == self.len {
+            return Poll::Ready(None);
+        }
+        self.next += 1;
+        Poll::Ready(Some(self.next - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.next, Some(self.len - self.next))
+    }
+}
+
+#[tokio::test]
+async fn test_stream() {
+    let mut stream = Counter { next: 0, len: 5 }.progress_with(ProgressBar::with_writer(5, io::sink()));
+    let mut sum = 0;
+    while let Some(n) = poll_fn(|cx| futures_core::Stream::poll_next(Pin::new(&mut stream), cx)).await {
+        sum += n;
+    }
+    assert_eq!(sum, 10);
+    assert_eq!(stream.progress_bar().position(), 0, "finalized once exhausted");
+
+    // Nothing is printed for a stream dropped before yielding any item
+    let buffer = SharedBuffer::default();
+    drop(Counter { next: 0, len: 5 }.progress_with(ProgressBar::with_writer(5, buffer.clone())));
+    assert_eq!(buffer.take(), "");
+}
+
+#[tokio::test]
+async fn test_async_read_write() {
+    let data: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+
+    let mut reader = ProgressBar::with_writer(data.len(), io::sink()).wrap_read(io::Cursor::new(data.clone()));
+    let mut writer = ProgressBar::with_writer(data.len(), io::sink()).wrap_write(Vec::new());
+    assert_eq!(tokio::io::copy(&mut reader, &mut writer).await.unwrap(), 10_000);
+    writer.flush().await.unwrap();
+    assert_eq!(reader.progress_bar().position(), 10_000);
+    assert_eq!(writer.progress_bar().position(), 10_000);
+    assert_eq!(writer.get_ref(), &data);
+
+    reader.seek(io::SeekFrom::Start(100)).await.unwrap();
+    assert_eq!(reader.progress_bar().position(), 100);
+    let mut buf = [0; 50];
+    reader.read_exact(&mut buf).await.unwrap();
+    assert_eq!(reader.progress_bar().position(), 150);
+
+    let mut lines = ProgressBar::with_writer(12, io::sink()).wrap_read(&b"first\nsecond"[..]);
+    let mut line = String::new();
+    lines.read_line(&mut line).await.unwrap();
+    assert_eq!(lines.progress_bar().position(), 6);
+}
+
+#[tokio::test]
+async fn test_global_async() {
+    init_progress_bar_with_writer(10, io::sink());
+    inc_progress_bar_async().await;
+    inc_progress_bar_by_async(2).await;
+    set_progress_bar_message_async("file.txt").await;
+    assert_eq!(with_progress_bar_async(|progress_bar| progress_bar.position()).await, Ok(3));
+
+    // Waits for another thread holding the bar
+    let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+    let holder = std::thread::spawn(move || {
+        let _current = CURRENT_PROGRESS_BAR.lock().unwrap();
+        locked_tx.send(()).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    });
+    locked_rx.recv().unwrap();
+    inc_progress_bar_async().await;
+    holder.join().unwrap();
+    assert_eq!(with_progress_bar_async(|progress_bar| progress_bar.position()).await, Ok(4));
+
+    finalize_progress_bar_async().await;
+    assert!(!has_progress_bar());
+    assert_eq!(with_progress_bar_async(|progress_bar| progress_bar.position()).await, Err(ProgressBarError::NoProgressBar));
+
+    // A panic while holding the bar poisons it
+    init_progress_bar_with_writer(10, io::sink());
+    let _ = std::thread::spawn(|| {
+        let _current = CURRENT_PROGRESS_BAR.lock().unwrap();
+        panic!("poisoned");
+    }).join();
+    assert_eq!(with_progress_bar_async(|progress_bar| progress_bar.position()).await, Err(ProgressBarError::Poisoned));
+}
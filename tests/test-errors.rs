@@ -0,0 +1,45 @@
+use progress_bar::{pb::ProgressBar, *};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Panics on the first write, to poison the global progress bar
+struct PanickingWriter(AtomicBool);
+
+impl std::io::Write for PanickingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if !self.0.swap(true, Ordering::Relaxed) {
+            panic!("broken writer");
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// The global bar and the error policy are shared, so the scenarios run one after the other
+#[test]
+fn test_errors() {
+    assert_eq!(try_inc_progress_bar(), Err(ProgressBarError::NoProgressBar));
+    assert_eq!(try_finalize_progress_bar(), Err(ProgressBarError::NoProgressBar));
+    assert_eq!(try_inc_progress_bar_named("download"), Err(ProgressBarError::NoProgressBarNamed("download".to_string())));
+    assert_eq!(ProgressBarError::NoProgressBarNamed("download".to_string()).to_string(), "no progress bar named download");
+
+    // Errors are ignored, or turned into panics, depending on the policy
+    set_progress_bar_error_policy(ErrorPolicy::Silent);
+    inc_progress_bar();
+    set_progress_bar_error_policy(ErrorPolicy::Panic);
+    assert!(std::panic::catch_unwind(inc_progress_bar).is_err());
+    set_progress_bar_error_policy(ErrorPolicy::Silent);
+
+    // A panic while updating the bar poisons it, until a new bar is set
+    set_progress_bar(ProgressBar::with_writer(10, PanickingWriter(AtomicBool::new(false))));
+    assert!(std::panic::catch_unwind(inc_progress_bar).is_err());
+    assert!(has_progress_bar());
+    assert_eq!(try_inc_progress_bar(), Err(ProgressBarError::Poisoned));
+    inc_progress_bar();
+    set_progress_bar(ProgressBar::with_writer(10, std::io::sink()));
+    assert_eq!(try_inc_progress_bar(), Ok(()));
+    assert_eq!(try_finalize_progress_bar(), Ok(()));
+    assert!(!has_progress_bar());
+}
@@ -0,0 +1,54 @@
+use progress_bar::estimator::{Estimator, Ewma, Linear, SlidingWindow};
+use progress_bar::pb::ProgressBar;
+use std::io;
+use std::time::{Duration, Instant};
+
+/// 10 steps per second for 10 seconds, then 100 steps per second for 2 seconds
+fn feed(estimator: &mut dyn Estimator) -> Instant {
+    let start = Instant::now();
+    estimator.reset(start);
+    let mut progress = 0;
+    for i in 1..=30 {
+        progress += 10;
+        let secs = if i <= 10 { i as f64 } else { 10. + (i - 10) as f64 * 0.1 };
+        estimator.record(progress, start + Duration::from_secs_f64(secs));
+    }
+    start + Duration::from_secs(12)
+}
+
+#[test]
+fn test_estimators() {
+    let mut linear = Linear::new();
+    let now = feed(&mut linear);
+    assert_eq!(linear.rate(now).map(f64::round), Some(25.));
+
+    let mut ewma = Ewma::new(Duration::from_millis(500));
+    let now = feed(&mut ewma);
+    assert!(ewma.rate(now).unwrap() > 90.);
+
+    let mut window = SlidingWindow::new(Duration::from_secs(1));
+    let now = feed(&mut window);
+    assert_eq!(window.rate(now).map(f64::round), Some(100.));
+    // Stalling lowers the rate
+    assert_eq!(window.rate(now + Duration::from_secs(1)).map(f64::round), Some(50.));
+}
+
+struct Fixed(f64);
+
+impl Estimator for Fixed {
+    fn reset(&mut self, _now: Instant) {}
+    fn record(&mut self, _progress: u64, _now: Instant) {}
+    fn rate(&self, _now: Instant) -> Option<f64> {
+        Some(self.0)
+    }
+}
+
+#[test]
+fn test_custom_estimator() {
+    let mut test = ProgressBar::with_writer(100, io::sink());
+    test.set_width(4);
+    test.enable_eta();
+    test.set_estimator(Fixed(2.));
+    test.set_progress(90);
+    assert_eq!(test.render_plain(), " [===>] 90/100 (ETA 5s)");
+}
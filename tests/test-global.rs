@@ -1,3 +1,7 @@
+mod common;
+
+use common::SharedBuffer;
+
 #[test]
 fn test() {
     use progress_bar::*;
@@ -25,3 +29,50 @@ fn test() {
     
     finalize_progress_bar();
 }
+
+#[test]
+fn test_named() {
+    use progress_bar::*;
+    use progress_bar::multi::MultiProgress;
+
+    let buffer = SharedBuffer::default();
+    let multi = MultiProgress::with_writer(buffer.clone());
+    multi.set_plain_mode(false);
+    multi.set_terminal_width(60);
+    set_progress_bar_registry(multi);
+    init_progress_bar_named("download", 10);
+    init_indeterminate_progress_bar_named("extract");
+    assert!(has_progress_bar_named("download"));
+    assert!(!has_progress_bar_named("upload"));
+    set_progress_bar_action_named("download", "Download", Color::Blue, Style::Bold);
+    set_progress_bar_action_named("extract", "Extract", Color::Blue, Style::Bold);
+    buffer.take();
+
+    // Named bars are rendered together, in one block
+    for _ in 0..10 {
+        inc_progress_bar_named("download");
+        inc_progress_bar_by_named("extract", 1024);
+    }
+    let frame = buffer.take();
+    let last_frame = &frame[frame.rfind('\r').unwrap()..];
+    assert!(last_frame.contains("Download\x1B[0m ["));
+    assert!(last_frame.contains("] 10/10"));
+    assert!(last_frame.contains("Extract\x1B[0m "));
+    assert!(last_frame.ends_with("\x1B[K\n\x1B[J\x1B[2A"));
+
+    set_progress_bar_message_named("extract", "archive.tar");
+    print_progress_bar_info_named("download", "Success", "downloaded", Color::Green, Style::Bold);
+    assert!(buffer.take().contains("downloaded"));
+
+    // Registering a name again finalizes the previous bar, leaving its finish line above the block
+    init_progress_bar_named("download", 5);
+    let output = buffer.take();
+    assert!(output.contains("] 10/10 (done in 0s)\x1B[K\n"));
+    assert!(output.ends_with("\x1B[J\x1B[2A"));
+
+    finalize_progress_bar_named("download");
+    assert!(!has_progress_bar_named("download"));
+    assert!(has_progress_bar_named("extract"));
+    finalize_progress_bar_named("extract");
+    assert!(!has_progress_bar_named("extract"));
+}
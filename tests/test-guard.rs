@@ -0,0 +1,59 @@
+mod common;
+
+use progress_bar::{pb::ProgressBar, *};
+use common::SharedBuffer;
+
+// The global bar is shared, so the scenarios run one after the other
+#[test]
+fn test_guard() {
+    let buffer = SharedBuffer::default();
+
+    // Returning early finalizes the bar
+    let load = |buffer: SharedBuffer| -> Result<(), ()> {
+        let _guard = set_progress_bar_guarded(ProgressBar::with_writer(10, buffer));
+        inc_progress_bar();
+        Err(())?;
+        inc_progress_bar();
+        Ok(())
+    };
+    assert!(load(buffer.clone()).is_err());
+    assert!(!has_progress_bar());
+    assert!(buffer.take().ends_with("] 1/10 (done in 0s)\n"));
+
+    // A bar finalized before the guard is dropped is left alone
+    let guard = set_progress_bar_guarded(ProgressBar::with_writer(10, buffer.clone()));
+    inc_progress_bar();
+    finalize_progress_bar();
+    buffer.take();
+    drop(guard);
+    assert!(buffer.take().is_empty());
+
+    // A guard leaves alone a bar set after its own was finalized
+    let guard = set_progress_bar_guarded(ProgressBar::with_writer(10, buffer.clone()));
+    finalize_progress_bar();
+    init_progress_bar_with_writer(10, buffer.clone());
+    inc_progress_bar();
+    buffer.take();
+    drop(guard);
+    assert!(has_progress_bar());
+    assert!(buffer.take().is_empty());
+
+    // Same if its bar was replaced
+    let guard = set_progress_bar_guarded(ProgressBar::with_writer(10, buffer.clone()));
+    set_progress_bar(ProgressBar::with_writer(10, buffer.clone()));
+    drop(guard);
+    assert!(has_progress_bar());
+    finalize_progress_bar();
+    buffer.take();
+
+    // Panicking abandons the bar
+    let writer = buffer.clone();
+    let result = std::panic::catch_unwind(move || {
+        let _guard = set_progress_bar_guarded(ProgressBar::with_writer(10, writer));
+        inc_progress_bar_by(3);
+        panic!("interrupted");
+    });
+    assert!(result.is_err());
+    assert!(!has_progress_bar());
+    assert!(buffer.take().ends_with("] 3/10 (abandoned after 0s)\n"));
+}
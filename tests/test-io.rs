@@ -0,0 +1,36 @@
+use progress_bar::pb::ProgressBar;
+use std::io::{self, BufRead, Cursor, Read, Seek, SeekFrom, Write};
+
+#[test]
+fn test_read_write() {
+    let data: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+
+    let mut reader = ProgressBar::with_writer(data.len(), io::sink()).wrap_read(Cursor::new(data.clone()));
+    let mut writer = ProgressBar::with_writer(data.len(), io::sink()).wrap_write(Vec::new());
+    assert_eq!(io::copy(&mut reader, &mut writer).unwrap(), 10_000);
+    assert_eq!(reader.progress_bar().position(), 10_000);
+    assert_eq!(writer.progress_bar().position(), 10_000);
+    writer.flush().unwrap();
+    let (written, _) = writer.into_inner();
+    assert_eq!(written, data);
+
+    // Seeking moves the bar
+    assert_eq!(reader.seek(SeekFrom::Start(2_000)).unwrap(), 2_000);
+    assert_eq!(reader.progress_bar().position(), 2_000);
+    let mut buf = [0; 500];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.progress_bar().position(), 2_500);
+
+    // Buffered reads count consumed bytes
+    reader.seek(SeekFrom::End(-100)).unwrap();
+    let available = reader.fill_buf().unwrap().len();
+    assert_eq!(available, 100);
+    reader.consume(40);
+    assert_eq!(reader.progress_bar().position(), 9_940);
+
+    let mut lines = ProgressBar::with_writer(12, io::sink()).wrap_read(Cursor::new("first\nsecond"));
+    let mut line = String::new();
+    lines.read_line(&mut line).unwrap();
+    assert_eq!(line, "first\n");
+    assert_eq!(lines.progress_bar().position(), 6);
+}
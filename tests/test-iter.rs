@@ -0,0 +1,42 @@
+mod common;
+
+use progress_bar::iter::ProgressIterator;
+use progress_bar::pb::ProgressBar;
+use common::SharedBuffer;
+
+#[test]
+fn test_iter() {
+    let sum: u32 = (1..=100).progress().sum();
+    assert_eq!(sum, 5050);
+
+    let buffer = SharedBuffer::default();
+    let mut progress_bar = ProgressBar::with_writer(4, buffer.clone());
+    progress_bar.set_width(4);
+    let mut iter = ["a", "b", "c", "d"].into_iter().progress_with(progress_bar);
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next(), Some("a"));
+    assert_eq!(iter.progress_bar().position(), 1);
+    assert_eq!(iter.by_ref().count(), 3);
+    // Finalized once exhausted
+    assert!(buffer.take().ends_with(" [===>] 4/4 (done in 0s)\n"));
+    drop(iter);
+    assert_eq!(buffer.take(), "");
+}
+
+#[test]
+fn test_iter_drop() {
+    let buffer = SharedBuffer::default();
+    let mut progress_bar = ProgressBar::with_writer(10, buffer.clone());
+    progress_bar.set_width(10);
+    for i in (0..10).progress_with(progress_bar) {
+        if i == 4 {
+            break;
+        }
+    }
+    // Finalized when dropped early
+    assert!(buffer.take().ends_with(" [====>     ] 5/10 (done in 0s)\n"));
+
+    // Nothing is printed if no item was yielded
+    drop((0..10).progress_with(ProgressBar::with_writer(10, buffer.clone())));
+    assert_eq!(buffer.take(), "");
+}
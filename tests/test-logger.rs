@@ -0,0 +1,35 @@
+mod common;
+
+use common::SharedBuffer;
+
+#[test]
+fn test_logger_named() {
+    use progress_bar::*;
+    use progress_bar::multi::MultiProgress;
+
+    init_logger_with_inner(InnerLogger::None).unwrap();
+    let buffer = SharedBuffer::default();
+    let multi = MultiProgress::with_writer(buffer.clone());
+    multi.set_plain_mode(false);
+    multi.set_terminal_width(60);
+    set_progress_bar_registry(multi);
+    init_progress_bar_named("download", 10);
+    inc_progress_bar_named("download");
+    buffer.take();
+
+    // Logs are printed above the block of named bars, which is redrawn below them
+    log::info!("hello");
+    let output = buffer.take();
+    assert!(output.starts_with("\r"));
+    assert!(output.contains("Info\x1B[0m hello\x1B[K\n"));
+    let last_frame = &output[output.rfind('\r').unwrap()..];
+    assert!(last_frame.contains("] 1/10"));
+    assert!(last_frame.ends_with("\x1B[K\n\x1B[J\x1B[1A"));
+
+    finalize_progress_bar_named("download");
+    buffer.take();
+
+    // Without any bar, the log is dropped
+    log::info!("dropped");
+    assert_eq!(buffer.take(), "");
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use progress_bar::multi::MultiProgress;
+use progress_bar::style::{Color, Style};
+use std::thread;
+use common::SharedBuffer;
+
+#[test]
+fn test_multi() {
+    let multi = MultiProgress::new();
+    let handles: Vec<_> = (0..4).map(|i| {
+        let progress_bar = multi.add(100);
+        progress_bar.set_action(&format!("Thread {i}"), Color::Blue, Style::Bold);
+        thread::spawn(move || {
+            for _ in 0..100 {
+                progress_bar.inc();
+                thread::sleep(std::time::Duration::from_millis(1));
+            }
+            progress_bar.finalize();
+        })
+    }).collect();
+
+    multi.print_info("Started", "4 threads", Color::LightGreen, Style::Bold);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn test_multi_terminal() {
+    let buffer = SharedBuffer::default();
+    let multi = MultiProgress::with_writer(buffer.clone());
+    multi.set_plain_mode(false);
+    multi.set_terminal_width(20);
+
+    let first = multi.add(4);
+    let second = multi.add(2);
+    buffer.take();
+
+    // Bars without action start with empty styling codes
+    const NO_ACTION: &str = "\x1B[0m\x1B[30m\x1B[0m";
+    let first_line = format!("{NO_ACTION} [            ] 0/4\x1B[K\n");
+    let second_line = format!("{NO_ACTION} [=====>      ] 1/2\x1B[K\n");
+
+    second.inc();
+    assert_eq!(buffer.take(), format!("\r{first_line}{second_line}\x1B[J\x1B[2A"));
+
+    multi.print_info("Info", "text", Color::Red, Style::Bold);
+    assert_eq!(buffer.take(), format!("\r\x1B[1m\x1B[31m        Info\x1B[0m text\x1B[K\n\r{first_line}{second_line}\x1B[J\x1B[2A"));
+
+    // The finish line gives the elapsed time, truncated to the terminal width
+    first.finalize();
+    let final_line = format!("{NO_ACTION} [] 0/4 (done in 0s\x1B[0m\x1B[K\n");
+    assert_eq!(buffer.take(), format!("\r{final_line}\r{second_line}\x1B[J\x1B[1A"));
+
+    first.inc();
+    assert_eq!(buffer.take(), "");
+
+    second.remove();
+    assert_eq!(buffer.take(), "\r\x1B[J");
+}
+
+#[test]
+fn test_multi_plain() {
+    let buffer = SharedBuffer::default();
+    let multi = MultiProgress::with_writer(buffer.clone());
+    let first = multi.add(10);
+    let second = multi.add(10);
+    for _ in 0..10 {
+        first.inc();
+    }
+    second.inc();
+    first.finalize();
+    second.finalize();
+
+    let output = buffer.take();
+    assert!(!output.contains('\x1B'));
+    assert_eq!(output.lines().filter(|line| line.ends_with("/10")).count(), 11 + 2);
+}
+
+#[test]
+fn test_multi_hierarchy() {
+    use progress_bar::multi::Aggregation;
+    use progress_bar::template::Template;
+
+    let buffer = SharedBuffer::default();
+    let multi = MultiProgress::with_writer(buffer.clone());
+    multi.set_plain_mode(false);
+    let template: Template = "{pos}/{len}".parse().unwrap();
+
+    let parent = multi.add(0);
+    let first = parent.add_child(4);
+    let second = parent.add_weighted_child(2, 6);
+    let grandchild = second.add_child(3);
+    for progress_bar in [&parent, &first, &second, &grandchild] {
+        progress_bar.update(|progress_bar| progress_bar.set_template(template.clone()));
+    }
+    buffer.take();
+
+    first.inc();
+    assert_eq!(buffer.take(), "\r1/10\x1B[K\n  1/4\x1B[K\n  0/3\x1B[K\n    0/3\x1B[K\n\x1B[J\x1B[4A");
+
+    grandchild.set_progress(3);
+    assert_eq!(buffer.take(), "\r7/10\x1B[K\n  1/4\x1B[K\n\x1B[J\x1B[2A");
+
+    parent.set_aggregation(Aggregation::Count);
+    assert_eq!(buffer.take(), "\r1/2\x1B[K\n  1/4\x1B[K\n\x1B[J\x1B[2A");
+
+    first.finalize();
+    assert_eq!(buffer.take(), "\r2/2\x1B[K\n\x1B[J\x1B[1A");
+
+    parent.finalize();
+    assert_eq!(buffer.take(), "\r2/2\x1B[K\n\r\x1B[J");
+    grandchild.inc();
+    assert_eq!(buffer.take(), "");
+}
+
+#[test]
+fn test_multi_large_counts() {
+    use progress_bar::template::Template;
+
+    const GIB: u64 = 1 << 30;
+    let buffer = SharedBuffer::default();
+    let multi = MultiProgress::with_writer(buffer.clone());
+    multi.set_plain_mode(false);
+    let template: Template = "{pos}/{len}".parse().unwrap();
+
+    // Byte counts above 4 GiB are aggregated without overflowing on 32-bit targets
+    let parent = multi.add(0);
+    let first = parent.add_child(0);
+    let second = parent.add_child(0);
+    parent.update(|progress_bar| progress_bar.set_template(template.clone()));
+    first.set_length(6 * GIB);
+    second.set_length(4 * GIB);
+    first.set_position(3 * GIB);
+    second.inc_by(GIB);
+    assert_eq!(parent.update(|progress_bar| (progress_bar.position(), progress_bar.length())), Some((4 * GIB, Some(10 * GIB))));
+    assert!(buffer.take().contains(&format!("\r{}/{}\x1B[K\n", 4 * GIB, 10 * GIB)));
+}
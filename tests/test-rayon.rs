@@ -0,0 +1,24 @@
+mod common;
+
+use progress_bar::parallel::ParallelProgressIterator;
+use progress_bar::pb::ProgressBar;
+use rayon::prelude::*;
+use common::SharedBuffer;
+
+#[test]
+fn test_par_iter() {
+    let sum: u64 = (0..100_000u64).into_par_iter().progress().map(|i| i * 2).sum();
+    assert_eq!(sum, 9_999_900_000);
+
+    let buffer = SharedBuffer::default();
+    let mut progress_bar = ProgressBar::with_writer(1000, buffer.clone());
+    progress_bar.set_width(10);
+    let evens: Vec<u32> = (0..1000).into_par_iter().progress_with(progress_bar).filter(|i| i % 2 == 0).collect();
+    assert_eq!(evens.len(), 500);
+    let output = buffer.contents();
+    assert!(output.ends_with(" [=========>] 1000/1000 (done in 0s)\n"), "{output}");
+
+    // Unindexed iterators
+    let count = (0..1000).into_par_iter().filter(|i| i % 3 == 0).progress().count();
+    assert_eq!(count, 334);
+}
@@ -0,0 +1,234 @@
+use progress_bar::pb::ProgressBar;
+use progress_bar::style::{Color, Style, Units};
+use std::io;
+
+#[test]
+fn test_render_layout() {
+    let mut test = ProgressBar::with_writer(10, io::sink());
+    test.set_width(10);
+    assert_eq!(test.render_plain(), " [          ] 0/10");
+
+    test.set_progress(5);
+    assert_eq!(test.render_plain(), " [====>     ] 5/10");
+
+    test.set_progress(10);
+    assert_eq!(test.render_plain(), " [=========>] 10/10");
+}
+
+#[test]
+fn test_render_action() {
+    let mut test = ProgressBar::with_writer(2, io::sink());
+    test.set_width(4);
+
+    test.set_action("Loading", Color::Blue, Style::Bold);
+    assert_eq!(test.render_plain(), "     Loading [    ] 0/2");
+    assert_eq!(test.render(), "\x1B[1m\x1B[34m     Loading\x1B[0m [    ] 0/2");
+
+    test.set_action("Decompressing", Color::Blue, Style::Bold);
+    assert_eq!(test.render_plain(), "Decompressin [    ] 0/2");
+
+    // Actions are aligned by display width, and cut on character boundaries
+    test.set_action("Téléchargé", Color::Blue, Style::Bold);
+    assert_eq!(test.render_plain(), "  Téléchargé [    ] 0/2");
+    test.set_action("Téléchargement", Color::Blue, Style::Bold);
+    assert_eq!(test.render_plain(), "Téléchargeme [    ] 0/2");
+    test.set_action("日本", Color::Blue, Style::Bold);
+    assert_eq!(test.render_plain(), "        日本 [    ] 0/2");
+    test.set_action("日本語のテキスト", Color::Blue, Style::Bold);
+    assert_eq!(test.render_plain(), "日本語のテキ [    ] 0/2");
+}
+
+#[test]
+fn test_render_eta() {
+    let mut test = ProgressBar::with_writer(4, io::sink());
+    test.set_width(4);
+    test.enable_eta();
+    assert_eq!(test.render_plain(), " [    ] 0/4");
+
+    test.inc();
+    assert!(test.render_plain().starts_with(" [>   ] 1/4 (ETA "));
+
+    test.set_progress(4);
+    assert_eq!(test.render_plain(), " [===>] 4/4");
+}
+
+#[test]
+fn test_render_terminal_width() {
+    let mut test = ProgressBar::with_writer(10, io::sink());
+    test.set_action("Loading", Color::Blue, Style::Bold);
+    test.set_progress(5);
+
+    test.set_terminal_width(40);
+    assert_eq!(test.render_plain(), "     Loading [========>         ] 5/10");
+
+    test.set_width(10);
+    assert_eq!(test.render_plain(), "     Loading [====>     ] 5/10");
+
+    test.set_terminal_width(25);
+    assert_eq!(test.render_plain(), "     Loading [=> ] 5/10");
+
+    test.set_terminal_width(10);
+    assert_eq!(test.render_plain(), "     Load");
+    assert_eq!(test.render(), "\x1B[1m\x1B[34m     Load\x1B[0m");
+}
+
+#[test]
+fn test_render_template() {
+    use progress_bar::template::{Template, TemplateError};
+
+    let mut test = ProgressBar::with_writer(200, io::sink());
+    test.set_action("Loading", Color::Blue, Style::Bold);
+    test.set_progress(50);
+
+    test.set_template(Template::parse("{action:^11}|{bar:8}|{percent:>4}% {pos:>5}/{len:<5}|{{eta}}").unwrap());
+    assert_eq!(test.render_plain(), "  Loading  |[=>      ]|  25%    50/200  |{eta}");
+    assert_eq!(test.render(), "\x1B[1m\x1B[34m  Loading  \x1B[0m|[=>      ]|  25%    50/200  |{eta}");
+
+    test.set_template("{pos} {bar} {len}".parse().unwrap());
+    test.set_terminal_width(20);
+    assert_eq!(test.render_plain(), "50 [==>       ] 200");
+
+    // The bar always fills its width, so alignment has no effect
+    test.set_template("{pos} {bar:^8} {len}".parse().unwrap());
+    assert_eq!(test.render_plain(), "50 [=>      ] 200");
+
+    assert_eq!(Template::parse("{bar"), Err(TemplateError::UnclosedPlaceholder));
+    assert_eq!(Template::parse("bar}"), Err(TemplateError::UnmatchedBrace));
+    assert_eq!(Template::parse("{foo}"), Err(TemplateError::UnknownPlaceholder("foo".to_string())));
+    assert_eq!(Template::parse("{bar:x}"), Err(TemplateError::InvalidSpec("x".to_string())));
+}
+
+#[test]
+fn test_render_smooth() {
+    use progress_bar::style::BarMode;
+
+    let mut test = ProgressBar::with_writer(80, io::sink());
+    test.set_width(10);
+    test.set_bar_mode(BarMode::Smooth);
+    assert_eq!(test.render_plain(), " [          ] 0/80");
+
+    test.set_progress(1);
+    assert_eq!(test.render_plain(), " [▏         ] 1/80");
+
+    test.set_progress(43);
+    assert_eq!(test.render_plain(), " [█████▍    ] 43/80");
+
+    test.set_progress(80);
+    assert_eq!(test.render_plain(), " [██████████] 80/80");
+}
+
+#[test]
+fn test_render_glyphs() {
+    use progress_bar::style::BarGlyphs;
+
+    let mut test = ProgressBar::with_writer(10, io::sink());
+    test.set_width(10);
+    test.set_bar_glyphs(BarGlyphs::new("🟩", "", "⬛", "|", "|"));
+    test.set_progress(5);
+    assert_eq!(test.render_plain(), " |🟩🟩⬛⬛⬛| 5/10");
+
+    test.set_bar_glyphs(BarGlyphs::new("-=", "🚀", ".", "<<", ">>"));
+    test.set_progress(6);
+    assert_eq!(test.render_plain(), " <<-=-=🚀....>> 6/10");
+
+    test.set_terminal_width(20);
+    test.set_template("{bar}{pos}".parse().unwrap());
+    assert_eq!(test.render_plain(), "<<-=-=🚀....>>6");
+}
+
+#[test]
+fn test_render_indeterminate() {
+    use progress_bar::spinner::{Indeterminate, Spinner};
+
+    let mut test = ProgressBar::with_writer(0, io::sink());
+    test.clear_max();
+    test.set_action("Walking", Color::Blue, Style::Bold);
+    assert!(test.render_plain().starts_with("     Walking - 0 (0s, "));
+    test.inc();
+    assert!(test.render_plain().starts_with("     Walking \\ 1 (0s, "));
+
+    test.set_indeterminate(Indeterminate::Spinner(Spinner::new(&["a", "b"])));
+    test.set_template("{spinner} {pos}/{len} {percent}%".parse().unwrap());
+    test.tick();
+    assert_eq!(test.render_plain(), "a 1/? ?%");
+
+    test.set_indeterminate(Indeterminate::Bounce);
+    test.set_template("{bar:6}".parse().unwrap());
+    let frames: Vec<String> = (0..8).map(|_| {
+        test.tick();
+        test.render_plain()
+    }).collect();
+    assert_eq!(frames, ["[   <=>]", "[  <=> ]", "[ <=>  ]", "[<=>   ]", "[ <=>  ]", "[  <=> ]", "[   <=>]", "[  <=> ]"]);
+
+    test.set_max(4);
+    assert_eq!(test.render_plain(), "[=>    ]");
+}
+
+#[test]
+fn test_render_bytes() {
+    use progress_bar::template::Template;
+
+    let mut test = ProgressBar::with_writer(0, io::sink());
+    test.set_width(4);
+    test.set_units(Units::BinaryBytes);
+    test.set_length(6 << 30);
+    test.inc_by(1536);
+    assert_eq!(test.render_plain(), " [>   ] 1.5 KiB/6.0 GiB");
+
+    test.set_position(3 << 30);
+    assert_eq!(test.position(), 3 << 30);
+    assert_eq!(test.render_plain(), " [=>  ] 3.0 GiB/6.0 GiB");
+
+    // Values rounding up to the next prefix are displayed with it
+    test.set_length(1 << 20);
+    test.set_position(1048473);
+    assert_eq!(test.render_plain(), " [===>] 1023.9 KiB/1.0 MiB");
+    test.set_position((1 << 20) - 1);
+    assert_eq!(test.render_plain(), " [===>] 1.0 MiB/1.0 MiB");
+    test.set_position(1023);
+    assert_eq!(test.render_plain(), " [>   ] 1023 B/1.0 MiB");
+
+    test.set_units(Units::DecimalBytes);
+    test.set_length(2_500_000);
+    test.set_position(999);
+    assert_eq!(test.render_plain(), " [>   ] 999 B/2.5 MB");
+
+    test.set_template(Template::parse("{pos}/{len} {percent}%").unwrap());
+    test.set_position(1_300_000);
+    assert_eq!(test.render_plain(), "1.3 MB/2.5 MB 52%");
+}
+
+#[test]
+fn test_render_percent_elapsed() {
+    use progress_bar::template::Template;
+
+    let mut test = ProgressBar::with_writer(8, io::sink());
+    test.set_width(4);
+    test.enable_percent();
+    test.enable_elapsed();
+    test.set_progress(2);
+    assert_eq!(test.render_plain(), " [>   ] 2/8 25% (elapsed 0s)");
+
+    test.set_template(Template::parse("{elapsed} {percent}%").unwrap());
+    assert_eq!(test.render_plain(), "0s 25%");
+}
+
+#[test]
+fn test_render_message() {
+    let mut test = ProgressBar::with_writer(10, io::sink());
+    test.set_progress(5);
+    test.set_terminal_width(40);
+
+    test.set_message("file.txt");
+    assert_eq!(test.render_plain(), " [==========>          ] 5/10 file.txt");
+
+    // Long messages are truncated, and the bar keeps a third of the line
+    test.set_message("a_very_long_file_name_that_does_not_fit.txt");
+    assert_eq!(test.render_plain(), " [========>        ] 5/10 a_very_long_f");
+
+    test.set_message("multi\nline");
+    assert!(test.render_plain().ends_with(" multi line"));
+
+    test.clear_message();
+    assert_eq!(test.render_plain(), " [==============>               ] 5/10");
+}
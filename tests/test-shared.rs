@@ -0,0 +1,55 @@
+mod common;
+
+use progress_bar::pb::ProgressBar;
+use progress_bar::shared::SharedProgressBar;
+use std::thread;
+use std::time::Duration;
+use common::SharedBuffer;
+
+#[test]
+fn test_shared() {
+    let buffer = SharedBuffer::default();
+    let mut progress_bar = ProgressBar::with_writer(400, buffer.clone());
+    progress_bar.set_plain_mode(false);
+    progress_bar.set_width(10);
+    let progress_bar = SharedProgressBar::with_render_interval(progress_bar, Duration::from_millis(5));
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            let progress_bar = progress_bar.clone();
+            scope.spawn(move || {
+                for _ in 0..50 {
+                    progress_bar.inc_by(2);
+                }
+            });
+        }
+    });
+    assert_eq!(progress_bar.position(), 400);
+
+    // Rendered in the background
+    thread::sleep(Duration::from_millis(50));
+    assert!(buffer.take().contains(" [=========>] 400/400\x1B[K"));
+
+    progress_bar.update(|progress_bar| progress_bar.set_message("done"));
+    progress_bar.finalize();
+    assert!(buffer.take().ends_with(" [=========>] 400/400 (done in 0s) done\x1B[K\n\x1B[1A\n"));
+
+    // Nothing is drawn after finalizing
+    progress_bar.inc();
+    thread::sleep(Duration::from_millis(20));
+    drop(progress_bar);
+    assert_eq!(buffer.take(), "");
+}
+
+#[test]
+fn test_shared_drop() {
+    let buffer = SharedBuffer::default();
+    let progress_bar = SharedProgressBar::new(ProgressBar::with_writer(10, buffer.clone()));
+    let handle = progress_bar.clone();
+    drop(progress_bar);
+    handle.set_progress(7);
+
+    // Finalized when the last handle is dropped
+    drop(handle);
+    assert!(buffer.take().contains("] 7/10 (done in 0s)"));
+}
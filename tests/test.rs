@@ -1,7 +1,12 @@
+mod common;
+
 use progress_bar::pb::ProgressBar;
 use progress_bar::style::{Color, Style};
+use progress_bar::ticker::Ticker;
 use std::time;
 use std::thread;
+use std::sync::{Arc, Mutex};
+use common::SharedBuffer;
 
 #[test]
 fn test() {
@@ -39,3 +44,174 @@ fn test() {
     }
     test.print_final_info("Loading", "Load complete", Color::LightGreen, Style::Bold);
 }
+
+#[test]
+fn test_writer() {
+    let buffer = SharedBuffer::default();
+    let mut test = ProgressBar::with_writer(3, buffer.clone());
+    test.set_action("Loading", Color::Blue, Style::Bold);
+    test.inc();
+    test.print_info("Found", "something", Color::LightGreen, Style::Normal);
+    test.inc();
+    test.finalize();
+
+    let output = buffer.contents();
+    assert!(output.contains("something"));
+    assert!(output.contains("] 1/3"));
+    assert!(output.contains("] 2/3"));
+    assert!(output.ends_with('\n'));
+}
+
+#[test]
+fn test_plain_mode() {
+    let buffer = SharedBuffer::default();
+    let mut test = ProgressBar::with_writer(100, buffer.clone());
+    test.set_action("Loading", Color::Blue, Style::Bold);
+    for i in 0..100 {
+        test.inc();
+        if i == 14 {
+            test.print_info("Failed", "to load a page", Color::Red, Style::Blink);
+        }
+    }
+    test.finalize();
+
+    let output = buffer.contents();
+    assert!(!output.contains('\x1B'));
+    assert!(output.contains("      Failed to load a page\n"));
+    let status_lines = output.lines().filter(|line| line.starts_with("     Loading [")).count();
+    // One line every 10%, and the finish line
+    assert_eq!(status_lines, 12);
+    assert!(output.ends_with("] 100/100 (done in 0s)\n"));
+}
+
+#[test]
+fn test_plain_mode_overshoot() {
+    // Going past the maximum, or having none yet, doesn't print a line per update
+    for max in [10, 0] {
+        let buffer = SharedBuffer::default();
+        let mut test = ProgressBar::with_writer(max, buffer.clone());
+        for _ in 0..1000 {
+            test.inc();
+        }
+        let output = buffer.contents();
+        assert!(output.lines().count() <= 11, "{} status lines for max {max}", output.lines().count());
+    }
+}
+
+#[test]
+fn test_redraw_rate() {
+    let buffer = SharedBuffer::default();
+    let mut test = ProgressBar::with_writer(10, buffer.clone());
+    test.set_plain_mode(false);
+    test.set_width(10);
+    test.set_max_redraw_rate(Some(1));
+
+    let frames = || buffer.contents().matches("\x1B[1A").count();
+    for _ in 0..5 {
+        test.inc();
+    }
+    // One frame for set_width, one for the first inc
+    assert_eq!(frames(), 2);
+
+    test.finalize();
+    assert_eq!(frames(), 3);
+    let output = buffer.contents();
+    assert!(output.ends_with("] 5/10 (done in 0s)\x1B[K\n\x1B[1A\n"));
+
+    test.set_progress(9);
+    test.inc();
+    assert_eq!(frames(), 4);
+}
+
+#[test]
+fn test_ticker() {
+    let buffer = SharedBuffer::default();
+    let mut progress_bar = ProgressBar::with_writer(10, buffer.clone());
+    progress_bar.set_plain_mode(false);
+    progress_bar.set_width(10);
+    let progress_bar = Arc::new(Mutex::new(progress_bar));
+
+    let frames = || buffer.contents().matches("\x1B[1A").count();
+    let ticker = Ticker::new(&progress_bar, time::Duration::from_millis(10));
+    thread::sleep(time::Duration::from_millis(100));
+    // Redrawn without any update
+    assert!(frames() > 3);
+
+    progress_bar.lock().unwrap().finalize();
+    thread::sleep(time::Duration::from_millis(30));
+    let after_finalize = frames();
+    thread::sleep(time::Duration::from_millis(50));
+    assert_eq!(frames(), after_finalize);
+    ticker.stop();
+
+    // Dropping the ticker stops it too
+    progress_bar.lock().unwrap().inc();
+    let ticker = Ticker::new(&progress_bar, time::Duration::from_millis(10));
+    drop(ticker);
+    let after_drop = frames();
+    thread::sleep(time::Duration::from_millis(50));
+    assert_eq!(frames(), after_drop);
+
+    // The ticker can be dropped while the bar is locked
+    for _ in 0..20 {
+        let ticker = Ticker::new(&progress_bar, time::Duration::from_millis(1));
+        thread::sleep(time::Duration::from_millis(2));
+        let mut locked = progress_bar.lock().unwrap();
+        locked.finalize();
+        drop(ticker);
+    }
+}
+
+#[test]
+fn test_rate() {
+    let buffer = SharedBuffer::default();
+    let mut test = ProgressBar::with_writer(10, buffer.clone());
+    test.set_width(10);
+    test.enable_rate();
+    for _ in 0..10 {
+        thread::sleep(time::Duration::from_millis(10));
+        test.inc();
+    }
+    test.finalize();
+
+    // The final line shows the average rate
+    let output = buffer.contents();
+    let last = output.lines().last().unwrap();
+    let rate = last.strip_prefix(" [=========>] 10/10 (").and_then(|rest| rest.strip_suffix("/s) (done in 0s)")).unwrap();
+    let rate: f64 = rate.parse().unwrap();
+    assert!(rate > 10. && rate <= 100., "{last}");
+}
+
+#[test]
+fn test_drop() {
+    // Dropping a displayed bar finalizes it
+    let buffer = SharedBuffer::default();
+    let mut test = ProgressBar::with_writer(10, buffer.clone());
+    test.inc();
+    drop(test);
+    let output = buffer.contents();
+    assert!(output.ends_with("] 1/10 (done in 0s)\n"));
+
+    // Bars that were never displayed, or already finalized, print nothing more
+    let buffer = SharedBuffer::default();
+    drop(ProgressBar::with_writer(10, buffer.clone()));
+    let mut test = ProgressBar::with_writer(10, buffer.clone());
+    test.inc();
+    test.finalize();
+    let finalized = buffer.contents().len();
+    drop(test);
+    assert_eq!(buffer.contents().len(), finalized);
+
+    // Bars dropped during a panic are abandoned
+    let buffer = SharedBuffer::default();
+    let writer = buffer.clone();
+    let result = std::panic::catch_unwind(move || {
+        let mut test = ProgressBar::with_writer(10, writer);
+        test.set_action("Téléchargé", Color::Blue, Style::Bold);
+        test.inc();
+        panic!("interrupted");
+    });
+    assert!(result.is_err());
+    let output = buffer.contents();
+    assert!(output.ends_with("] 1/10 (abandoned after 0s)\n"));
+}